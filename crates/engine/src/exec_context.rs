@@ -45,6 +45,13 @@ pub struct ExecContext {
     pub block_timestamp: BlockTimestamp,
     /// Known contract accounts
     pub contracts: Vec<Vec<u8>>,
+    /// The mock gas consumed by storage operations so far.
+    pub gas_used: u64,
+    /// The upper bound for `gas_used`. Storage operations which would
+    /// exceed it panic. There is no limit if this is `None`.
+    pub gas_limit: Option<u64>,
+    /// The depth of nested calls currently being executed.
+    pub call_depth: u32,
 }
 
 impl ExecContext {
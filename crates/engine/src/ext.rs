@@ -24,6 +24,8 @@ use crate::{
     test_api::{
         DebugInfo,
         EmittedEvent,
+        ReturnedValue,
+        StorageAccess,
     },
     types::{
         AccountId,
@@ -60,6 +62,13 @@ pub struct ChainSpec {
     pub minimum_balance: Balance,
     /// The targeted block time.
     pub block_time: BlockTimestamp,
+    /// The mock gas charged for every storage read.
+    pub storage_read_weight: u64,
+    /// The mock gas charged for every storage write or removal.
+    pub storage_write_weight: u64,
+    /// The maximum length in bytes of a single encoded storage value.
+    /// There is no limit if this is `None`.
+    pub max_value_len: Option<usize>,
 }
 
 /// The default values for the chain specification are:
@@ -67,6 +76,9 @@ pub struct ChainSpec {
 ///   * `gas_price`: 100
 ///   * `minimum_balance`: 42
 ///   * `block_time`: 6
+///   * `storage_read_weight`: 1
+///   * `storage_write_weight`: 10
+///   * `max_value_len`: `None`
 ///
 /// There is no particular reason behind choosing them this way.
 impl Default for ChainSpec {
@@ -75,6 +87,9 @@ impl Default for ChainSpec {
             gas_price: 100,
             minimum_balance: 1000000,
             block_time: 6,
+            storage_read_weight: 1,
+            storage_write_weight: 10,
+            max_value_len: None,
         }
     }
 }
@@ -150,11 +165,28 @@ impl Engine {
 
     /// Writes the encoded value into the storage at the given key.
     /// Returns the size of the previously stored value at the key if any.
+    ///
+    /// # Panics
+    ///
+    /// If the encoded value exceeds the `max_value_len` of the chain specification.
     pub fn set_storage(&mut self, key: &[u8], encoded_value: &[u8]) -> Option<u32> {
+        if let Some(max_value_len) = self.chain_spec.max_value_len {
+            assert!(
+                encoded_value.len() <= max_value_len,
+                "the encoded storage value is of size {} bytes, \
+                which exceeds the maximum value length of {} bytes",
+                encoded_value.len(),
+                max_value_len,
+            );
+        }
+
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
 
+        self.charge_gas(self.chain_spec.storage_write_weight);
         self.debug_info.inc_writes(account_id.clone());
+        self.debug_info
+            .record_storage_access(StorageAccess::Write(key.to_vec()));
         self.debug_info
             .record_cell_for_account(account_id, key.to_vec());
 
@@ -168,7 +200,10 @@ impl Engine {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
 
+        self.charge_gas(self.chain_spec.storage_read_weight);
         self.debug_info.inc_reads(account_id);
+        self.debug_info
+            .record_storage_access(StorageAccess::Read(key.to_vec()));
         match self.database.get_from_contract_storage(&callee, key) {
             Some(val) => Ok(val),
             None => Err(Error::KeyNotFound),
@@ -181,7 +216,10 @@ impl Engine {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
 
+        self.charge_gas(self.chain_spec.storage_write_weight);
         self.debug_info.inc_writes(account_id);
+        self.debug_info
+            .record_storage_access(StorageAccess::Clear(key.to_vec()));
         match self.database.remove_contract_storage(&callee, key) {
             Some(val) => Ok(val),
             None => Err(Error::KeyNotFound),
@@ -193,7 +231,10 @@ impl Engine {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
 
+        self.charge_gas(self.chain_spec.storage_read_weight);
         self.debug_info.inc_reads(account_id);
+        self.debug_info
+            .record_storage_access(StorageAccess::Read(key.to_vec()));
         self.database
             .get_from_contract_storage(&callee, key)
             .map(|val| val.len() as u32)
@@ -204,7 +245,10 @@ impl Engine {
     pub fn clear_storage(&mut self, key: &[u8]) -> Option<u32> {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
+        self.charge_gas(self.chain_spec.storage_write_weight);
         self.debug_info.inc_writes(account_id.clone());
+        self.debug_info
+            .record_storage_access(StorageAccess::Clear(key.to_vec()));
         let _ = self
             .debug_info
             .remove_cell_for_account(account_id, key.to_vec());
@@ -213,6 +257,23 @@ impl Engine {
             .map(|val| val.len() as u32)
     }
 
+    /// Adds `amount` to the mock gas used by the current execution.
+    ///
+    /// # Panics
+    ///
+    /// If the gas used would exceed the gas limit set for the execution.
+    fn charge_gas(&mut self, amount: u64) {
+        let gas_used = self.exec_context.gas_used.saturating_add(amount);
+        if let Some(gas_limit) = self.exec_context.gas_limit {
+            assert!(
+                gas_used <= gas_limit,
+                "out of gas: the gas limit of {gas_limit} would be exceeded \
+                by using {gas_used}",
+            );
+        }
+        self.exec_context.gas_used = gas_used;
+    }
+
     /// Remove the calling account and transfer remaining balance.
     ///
     /// This function never returns. Either the termination was successful and the
@@ -235,6 +296,20 @@ impl Engine {
         panic_any(scale::Encode::encode(&res));
     }
 
+    /// Returns the encoded value back to the caller of the executed contract.
+    ///
+    /// This function never returns. The value is recorded and the execution is
+    /// halted by panicking with a [`ReturnedValue`], so that tests can catch the
+    /// unwinding and inspect what was returned.
+    pub fn return_value(&mut self, reverted: bool, return_value: &[u8]) -> ! {
+        let returned = ReturnedValue {
+            reverted,
+            data: return_value.to_vec(),
+        };
+        self.debug_info.record_returned_value(returned.clone());
+        panic_any(returned);
+    }
+
     /// Returns the address of the caller.
     pub fn caller(&self, output: &mut &mut [u8]) {
         let caller = self
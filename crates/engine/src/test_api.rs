@@ -13,7 +13,10 @@
 // limitations under the License.
 
 use crate::{
-    ext::Engine,
+    ext::{
+        ChainSpec,
+        Engine,
+    },
     types::{
         AccountId,
         Balance,
@@ -34,6 +37,31 @@ pub struct EmittedEvent {
     pub data: Vec<u8>,
 }
 
+/// Record for an access to the contract storage.
+///
+/// Each variant holds the encoded storage key which was accessed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageAccess {
+    /// A value was read or checked for existence.
+    Read(Vec<u8>),
+    /// A value was written.
+    Write(Vec<u8>),
+    /// A value was removed, including taking it out of the storage.
+    Clear(Vec<u8>),
+}
+
+/// Record for a value returned from a contract via `return_value`.
+///
+/// The engine also unwinds with this record as the panic payload, so that it can not
+/// be confused with the payload of a contract termination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReturnedValue {
+    /// Whether the execution was reverted.
+    pub reverted: bool,
+    /// The encoded value.
+    pub data: Vec<u8>,
+}
+
 #[derive(Clone)]
 pub struct RecordedDebugMessages {
     debug_messages: Vec<String>,
@@ -85,6 +113,10 @@ pub struct DebugInfo {
     count_writes: HashMap<AccountId, usize>,
     /// The number of storage cells used by each account id.
     cells_per_account: HashMap<AccountId, HashMap<Vec<u8>, bool>>,
+    /// Storage accesses recorder.
+    storage_accesses: Vec<StorageAccess>,
+    /// The value returned by the last `return_value` call.
+    returned_value: Option<ReturnedValue>,
 }
 
 impl Default for DebugInfo {
@@ -102,6 +134,8 @@ impl DebugInfo {
             count_reads: HashMap::new(),
             count_writes: HashMap::new(),
             cells_per_account: HashMap::new(),
+            storage_accesses: Vec::new(),
+            returned_value: None,
         }
     }
 
@@ -112,6 +146,8 @@ impl DebugInfo {
         self.emitted_events.clear();
         self.emitted_debug_messages.clear();
         self.cells_per_account.clear();
+        self.storage_accesses.clear();
+        self.returned_value = None;
     }
 
     /// Increases the number of storage writes for the supplied account by one.
@@ -170,6 +206,16 @@ impl DebugInfo {
     pub fn record_event(&mut self, event: EmittedEvent) {
         self.emitted_events.push(event);
     }
+
+    /// Records an access to the storage.
+    pub fn record_storage_access(&mut self, access: StorageAccess) {
+        self.storage_accesses.push(access);
+    }
+
+    /// Records the value returned to the caller.
+    pub fn record_returned_value(&mut self, value: ReturnedValue) {
+        self.returned_value = Some(value);
+    }
 }
 
 impl Engine {
@@ -178,6 +224,7 @@ impl Engine {
         self.exec_context.reset();
         self.database.clear();
         self.debug_info.reset();
+        self.chain_spec = ChainSpec::default();
     }
 
     /// Returns the total number of reads and writes of the contract's storage.
@@ -198,6 +245,61 @@ impl Engine {
         self.debug_info.count_writes.values().sum()
     }
 
+    /// Returns the mock gas used by storage operations so far.
+    pub fn gas_used(&self) -> u64 {
+        self.exec_context.gas_used
+    }
+
+    /// Sets the gas limit for storage operations, or removes it if `gas_limit` is
+    /// `None`.
+    ///
+    /// Any storage operation which would make the used gas exceed `gas_limit`
+    /// panics.
+    pub fn set_gas_limit(&mut self, gas_limit: Option<u64>) {
+        self.exec_context.gas_limit = gas_limit;
+    }
+
+    /// Sets the mock gas charged for each storage read and write.
+    pub fn set_storage_weights(&mut self, read_weight: u64, write_weight: u64) {
+        self.chain_spec.storage_read_weight = read_weight;
+        self.chain_spec.storage_write_weight = write_weight;
+    }
+
+    /// Sets the maximum length in bytes of a single encoded storage value, or
+    /// removes the limit if `max_value_len` is `None`.
+    ///
+    /// Writing a value which exceeds `max_value_len` panics.
+    pub fn set_max_value_len(&mut self, max_value_len: Option<usize>) {
+        self.chain_spec.max_value_len = max_value_len;
+    }
+
+    /// Returns the depth of nested calls currently being executed.
+    pub fn call_depth(&self) -> u32 {
+        self.exec_context.call_depth
+    }
+
+    /// Records that a nested call has been entered.
+    pub fn enter_call(&mut self) {
+        self.exec_context.call_depth = self
+            .exec_context
+            .call_depth
+            .checked_add(1)
+            .expect("call depth overflowed");
+    }
+
+    /// Records that the innermost nested call has been exited.
+    ///
+    /// # Panics
+    ///
+    /// If no call has been entered.
+    pub fn exit_call(&mut self) {
+        self.exec_context.call_depth = self
+            .exec_context
+            .call_depth
+            .checked_sub(1)
+            .expect("exited more calls than were entered");
+    }
+
     /// Sets a caller for the next call.
     pub fn set_caller(&mut self, caller: Vec<u8>) {
         self.exec_context.caller = Some(caller.into());
@@ -227,6 +329,42 @@ impl Engine {
         Ok(cells.len())
     }
 
+    /// Returns all occupied contract storage cells of the account `account_id`
+    /// together with their raw values, sorted by storage key.
+    pub fn dump_contract_storage(&self, account_id: Vec<u8>) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut cells: Vec<(Vec<u8>, Vec<u8>)> = self
+            .debug_info
+            .cells_per_account
+            .get(&AccountId::from(account_id.clone()))
+            .into_iter()
+            .flat_map(|cells| cells.keys())
+            .filter_map(|key| {
+                self.database
+                    .get_from_contract_storage(&account_id, key)
+                    .map(|value| (key.clone(), value.clone()))
+            })
+            .collect();
+        cells.sort();
+        cells
+    }
+
+    /// Writes the encoded value into the storage of the account `account_id` at the
+    /// given key.
+    ///
+    /// Unlike [`Engine::set_storage`] this neither counts as a write nor consumes gas,
+    /// so it can be used to set up the initial storage state of a test.
+    pub fn seed_contract_storage(
+        &mut self,
+        account_id: Vec<u8>,
+        key: &[u8],
+        encoded_value: Vec<u8>,
+    ) {
+        self.debug_info
+            .record_cell_for_account(AccountId::from(account_id.clone()), key.to_vec());
+        self.database
+            .insert_into_contract_storage(&account_id, key, encoded_value);
+    }
+
     /// Advances the chain by a single block.
     pub fn advance_block(&mut self) {
         self.exec_context.block_number += 1;
@@ -253,6 +391,16 @@ impl Engine {
         self.debug_info.emitted_events.clone().into_iter()
     }
 
+    /// Returns all recorded accesses to the storage, in order.
+    pub fn get_storage_accesses(&self) -> Vec<StorageAccess> {
+        self.debug_info.storage_accesses.clone()
+    }
+
+    /// Returns the value of the last `return_value` call, if any.
+    pub fn get_returned_value(&self) -> Option<ReturnedValue> {
+        self.debug_info.returned_value.clone()
+    }
+
     /// Returns the current balance of `account_id`.
     pub fn get_balance(&self, account_id: Vec<u8>) -> Result<Balance, Error> {
         self.database
@@ -279,6 +427,11 @@ impl Engine {
     pub fn set_block_number(&mut self, new_block_number: BlockNumber) {
         self.exec_context.block_number = new_block_number;
     }
+
+    /// Set the time by which the block timestamp advances with every block.
+    pub fn set_block_time(&mut self, new_block_time: BlockTimestamp) {
+        self.chain_spec.block_time = new_block_time;
+    }
 }
 
 #[cfg(test)]
@@ -349,4 +502,158 @@ mod tests {
         assert_eq!(engine.count_writes(), 3);
         assert_eq!(engine.count_reads(), 2);
     }
+
+    #[test]
+    fn gas_used_accumulates_storage_weights() {
+        // given
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+        engine.set_storage_weights(2, 5);
+        let key: &[u8; 32] = &[0x42; 32];
+
+        // when
+        engine.set_storage(key, &[0x05_u8; 5]);
+        engine.get_storage(key).unwrap();
+        engine.clear_storage(key);
+
+        // then
+        assert_eq!(engine.gas_used(), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of gas")]
+    fn exceeding_gas_limit_panics() {
+        // given
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+        engine.set_storage_weights(1, 10);
+        engine.set_gas_limit(Some(25));
+        let key: &[u8; 32] = &[0x42; 32];
+
+        // when
+        engine.set_storage(key, &[0x05_u8; 5]);
+        engine.set_storage(key, &[0x05_u8; 6]);
+        assert_eq!(engine.gas_used(), 20);
+
+        // then
+        // the third write exceeds the limit
+        engine.set_storage(key, &[0x05_u8; 7]);
+    }
+
+    #[test]
+    fn value_within_max_value_len_is_stored() {
+        // given
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+        engine.set_max_value_len(Some(16));
+        let key: &[u8; 32] = &[0x42; 32];
+
+        // when
+        engine.set_storage(key, &[0x05_u8; 16]);
+
+        // then
+        assert_eq!(engine.get_storage(key), Ok(&[0x05_u8; 16][..]));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the maximum value length of 16 bytes")]
+    fn value_exceeding_max_value_len_panics() {
+        // given
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+        engine.set_max_value_len(Some(16));
+        let key: &[u8; 32] = &[0x42; 32];
+
+        // when
+        engine.set_storage(key, &[0x05_u8; 40]);
+    }
+
+    #[test]
+    fn max_value_len_can_be_removed() {
+        // given
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+        engine.set_max_value_len(Some(16));
+        let key: &[u8; 32] = &[0x42; 32];
+
+        // when
+        engine.set_max_value_len(None);
+        engine.set_storage(key, &[0x05_u8; 40]);
+
+        // then
+        assert_eq!(engine.get_storage(key), Ok(&[0x05_u8; 40][..]));
+    }
+
+    #[test]
+    fn dump_contract_storage_returns_sorted_cells() {
+        // given
+        let mut engine = Engine::new();
+        let account_id = vec![1; 32];
+        engine.set_callee(account_id.clone());
+        engine.set_storage(&[0x43], &[0x02]);
+        engine.set_storage(&[0x42], &[0x01, 0x01]);
+        engine.set_storage(&[0x44], &[0x03]);
+        engine.take_storage(&[0x44]).unwrap();
+
+        engine.set_callee(vec![2; 32]);
+        engine.set_storage(&[0x41], &[0x04]);
+
+        // when
+        let dump = engine.dump_contract_storage(account_id);
+
+        // then
+        assert_eq!(
+            dump,
+            vec![(vec![0x42], vec![0x01, 0x01]), (vec![0x43], vec![0x02])]
+        );
+    }
+
+    #[test]
+    fn seeded_storage_is_readable_without_counting_writes() {
+        // given
+        let mut engine = Engine::new();
+        let account_id = vec![1; 32];
+        engine.set_callee(account_id.clone());
+
+        // when
+        engine.seed_contract_storage(account_id.clone(), &[0x42], vec![0x01]);
+
+        // then
+        assert_eq!(engine.get_contract_storage_rw(account_id.clone()), (0, 0));
+        assert_eq!(engine.get_storage(&[0x42]), Ok(&[0x01_u8][..]));
+        assert_eq!(engine.count_used_storage_cells(&account_id), Ok(1));
+        assert_eq!(engine.gas_used(), engine.chain_spec.storage_read_weight);
+    }
+
+    #[test]
+    fn storage_accesses_are_recorded_in_order() {
+        // given
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+
+        // when
+        let _ = engine.get_storage(&[0x42]);
+        engine.set_storage(&[0x42], &[0x01]);
+        let _ = engine.contains_storage(&[0x43]);
+        let _ = engine.take_storage(&[0x42]);
+        engine.clear_storage(&[0x43]);
+
+        // then
+        assert_eq!(
+            engine.get_storage_accesses(),
+            vec![
+                StorageAccess::Read(vec![0x42]),
+                StorageAccess::Write(vec![0x42]),
+                StorageAccess::Read(vec![0x43]),
+                StorageAccess::Clear(vec![0x42]),
+                StorageAccess::Clear(vec![0x43]),
+            ]
+        );
+
+        // when
+        engine.initialize_or_reset();
+
+        // then
+        assert!(engine.get_storage_accesses().is_empty());
+    }
 }
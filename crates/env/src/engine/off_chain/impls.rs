@@ -234,11 +234,13 @@ impl EnvBackend for EnvInstance {
         unimplemented!("the off-chain env does not implement `input`")
     }
 
-    fn return_value<R>(&mut self, _flags: ReturnFlags, _return_value: &R) -> !
+    fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
     where
         R: scale::Encode,
     {
-        unimplemented!("the off-chain env does not implement `return_value`")
+        let buffer = scale::Encode::encode(return_value);
+        let reverted = flags.contains(ReturnFlags::REVERT);
+        self.engine.return_value(reverted, &buffer[..])
     }
 
     fn debug_message(&mut self, message: &str) {
@@ -27,7 +27,13 @@ use ink_engine::test_api::RecordedDebugMessages;
 use std::panic::UnwindSafe;
 
 pub use super::call_data::CallData;
-pub use ink_engine::ChainExtension;
+pub use ink_engine::{
+    test_api::{
+        ReturnedValue,
+        StorageAccess,
+    },
+    ChainExtension,
+};
 
 /// Record for an emitted event.
 #[derive(Clone)]
@@ -202,6 +208,112 @@ where
     })
 }
 
+/// Asserts that exactly `expected` storage reads were performed.
+///
+/// Both the count and the keys listed on failure are taken from
+/// [`recorded_storage_accesses`], so reads of every account's storage are included.
+///
+/// # Panics
+///
+/// If the number of reads differs. The message lists the storage keys which were read.
+pub fn expect_reads(expected: usize) {
+    let keys: Vec<_> = recorded_storage_accesses()
+        .into_iter()
+        .filter_map(|access| {
+            match access {
+                StorageAccess::Read(key) => Some(key),
+                StorageAccess::Write(_) | StorageAccess::Clear(_) => None,
+            }
+        })
+        .collect();
+    let reads = keys.len();
+    if reads != expected {
+        panic!(
+            "expected {expected} storage reads, but {reads} were performed \
+            (read keys: {keys:?})"
+        );
+    }
+}
+
+/// Asserts that exactly `expected` storage writes were performed, counting removals
+/// as writes.
+///
+/// Both the count and the keys listed on failure are taken from
+/// [`recorded_storage_accesses`], so writes to every account's storage are included.
+///
+/// # Panics
+///
+/// If the number of writes differs. The message lists the storage keys which were
+/// written or cleared.
+pub fn expect_writes(expected: usize) {
+    let keys: Vec<_> = recorded_storage_accesses()
+        .into_iter()
+        .filter_map(|access| {
+            match access {
+                StorageAccess::Write(key) | StorageAccess::Clear(key) => Some(key),
+                StorageAccess::Read(_) => None,
+            }
+        })
+        .collect();
+    let writes = keys.len();
+    if writes != expected {
+        panic!(
+            "expected {expected} storage writes, but {writes} were performed \
+            (written keys: {keys:?})"
+        );
+    }
+}
+
+/// Returns the mock gas used by storage operations so far.
+pub fn gas_used() -> u64 {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.engine.gas_used())
+}
+
+/// Sets the gas limit for storage operations, or removes it if `gas_limit` is `None`.
+///
+/// Any storage read or write which would make the used gas exceed `gas_limit`
+/// panics with an "out of gas" message. By default there is no limit.
+pub fn set_gas_limit(gas_limit: Option<u64>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_gas_limit(gas_limit);
+    })
+}
+
+/// Sets the mock gas charged for each storage read and write.
+pub fn set_storage_weights(read_weight: u64, write_weight: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .set_storage_weights(read_weight, write_weight);
+    })
+}
+
+/// Sets the maximum length in bytes of a single encoded storage value, or removes
+/// the limit if `max_value_len` is `None`.
+///
+/// Writing a value to the contract storage which exceeds `max_value_len` panics.
+/// By default there is no limit.
+pub fn set_max_value_len(max_value_len: Option<usize>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_max_value_len(max_value_len);
+    })
+}
+
+/// Returns all occupied contract storage cells of the account `account_id`
+/// together with their raw values, sorted by storage key.
+///
+/// The storage keys are returned in their SCALE encoded form.
+pub fn dump_contract_storage<T>(account_id: &T::AccountId) -> Vec<(Vec<u8>, Vec<u8>)>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .dump_contract_storage(scale::Encode::encode(&account_id))
+    })
+}
+
 /// Sets the value transferred from the caller to the callee as part of the call.
 ///
 /// Please note that the acting accounts should be set with [`set_caller()`] and
@@ -289,6 +401,55 @@ where
     })
 }
 
+/// Sets the time by which the block timestamp advances with every [`advance_block`]
+/// invocation.
+pub fn set_block_time<T>(value: T::Timestamp)
+where
+    T: Environment<Timestamp = u64>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_block_time(value);
+    })
+}
+
+/// Guard for a simulated nested call, see [`enter_call`].
+///
+/// The call is exited once the guard is dropped. If the engine was reset in the
+/// meantime, e.g. by [`run_test`], dropping the guard does nothing.
+#[must_use = "the call is exited as soon as the guard is dropped"]
+pub struct CallGuard {
+    /// The call depth right after entering the call.
+    depth: u32,
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            if instance.engine.call_depth() == self.depth {
+                instance.engine.exit_call();
+            }
+        })
+    }
+}
+
+/// Simulates entering a nested call, e.g. a cross-contract call.
+///
+/// Returns a guard which exits the call again when dropped. Together with
+/// [`call_depth`] this allows test harnesses to assert that a section isn't
+/// re-entered.
+pub fn enter_call() -> CallGuard {
+    let depth = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.enter_call();
+        instance.engine.call_depth()
+    });
+    CallGuard { depth }
+}
+
+/// Returns the depth of nested calls entered via [`enter_call`].
+pub fn call_depth() -> u32 {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.engine.call_depth())
+}
+
 /// Runs the given closure test function with the default configuration
 /// for the off-chain environment.
 pub fn run_test<T, F>(f: F) -> Result<()>
@@ -328,6 +489,35 @@ where
     f(default_accounts)
 }
 
+/// Runs the given closure test function like [`run_test`], but with the storage of
+/// the callee seeded with the given `(key, encoded_value)` entries first.
+///
+/// Seeding the storage neither counts as writes nor consumes gas.
+pub fn run_test_with_storage<T, K, F>(
+    storage: impl IntoIterator<Item = (K, Vec<u8>)>,
+    f: F,
+) -> Result<()>
+where
+    T: Environment,
+    K: scale::Encode,
+    F: FnOnce(DefaultAccounts<T>) -> Result<()>,
+    <T as Environment>::AccountId: From<[u8; 32]>,
+{
+    run_test::<T, _>(|default_accounts| {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            let callee = instance.engine.get_callee();
+            for (key, encoded_value) in storage {
+                instance.engine.seed_contract_storage(
+                    callee.clone(),
+                    &scale::Encode::encode(&key),
+                    encoded_value,
+                );
+            }
+        });
+        f(default_accounts)
+    })
+}
+
 /// Returns the default accounts for testing purposes:
 /// Alice, Bob, Charlie, Django, Eve and Frank.
 pub fn default_accounts<T>() -> DefaultAccounts<T>
@@ -374,6 +564,25 @@ pub fn recorded_events() -> impl Iterator<Item = EmittedEvent> {
     })
 }
 
+/// Returns the recorded accesses to the contract storage in order.
+pub fn recorded_storage_accesses() -> Vec<StorageAccess> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.get_storage_accesses()
+    })
+}
+
+/// Returns the encoded value of the last `return_value` call, if any, together with
+/// whether the execution was reverted.
+///
+/// Since `return_value` halts the execution by unwinding, the call has to be wrapped
+/// in [`std::panic::catch_unwind`] to inspect the value afterwards. The unwinding
+/// carries the same [`ReturnedValue`] as its panic payload.
+pub fn returned_value() -> Option<ReturnedValue> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.get_returned_value()
+    })
+}
+
 /// Tests if a contract terminates successfully after `self.env().terminate()`
 /// has been called.
 ///
@@ -41,3 +41,320 @@ fn topics_builder() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn caller_returns_mocked_caller() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::set_caller::<crate::DefaultEnvironment>(accounts.bob);
+
+        // then
+        assert_eq!(crate::caller::<crate::DefaultEnvironment>(), accounts.bob);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn transferred_value_returns_mocked_value() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        assert_eq!(crate::transferred_value::<crate::DefaultEnvironment>(), 0);
+
+        // when
+        crate::test::set_value_transferred::<crate::DefaultEnvironment>(1337);
+
+        // then
+        assert_eq!(
+            crate::transferred_value::<crate::DefaultEnvironment>(),
+            1337
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn advance_block_progresses_block_number_and_timestamp() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::set_block_number::<crate::DefaultEnvironment>(10);
+        crate::test::set_block_timestamp::<crate::DefaultEnvironment>(1000);
+        crate::test::set_block_time::<crate::DefaultEnvironment>(500);
+
+        // when
+        crate::test::advance_block::<crate::DefaultEnvironment>();
+        crate::test::advance_block::<crate::DefaultEnvironment>();
+
+        // then
+        assert_eq!(crate::block_number::<crate::DefaultEnvironment>(), 12);
+        assert_eq!(crate::block_timestamp::<crate::DefaultEnvironment>(), 2000);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn call_depth_follows_call_guards() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        assert_eq!(crate::test::call_depth(), 0);
+
+        {
+            let _outer = crate::test::enter_call();
+            assert_eq!(crate::test::call_depth(), 1);
+            {
+                let _inner = crate::test::enter_call();
+                assert_eq!(crate::test::call_depth(), 2);
+            }
+            assert_eq!(crate::test::call_depth(), 1);
+        }
+
+        assert_eq!(crate::test::call_depth(), 0);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn call_guard_dropped_after_reset_does_nothing() -> Result<()> {
+    // given
+    let mut guard = None;
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        guard = Some(crate::test::enter_call());
+        assert_eq!(crate::test::call_depth(), 1);
+
+        Ok(())
+    })?;
+
+    // when
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        drop(guard);
+
+        // then
+        assert_eq!(crate::test::call_depth(), 0);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn run_test_with_storage_seeds_callee_storage() -> Result<()> {
+    let seed = [(7u32, scale::Encode::encode(&42u32))];
+    crate::test::run_test_with_storage::<crate::DefaultEnvironment, _, _>(seed, |_| {
+        // given
+        let contract = crate::test::callee::<crate::DefaultEnvironment>();
+
+        // when
+        let value = crate::get_contract_storage::<u32, u32>(&7);
+
+        // then
+        assert_eq!(value, Ok(Some(42)));
+        assert_eq!(
+            crate::test::get_contract_storage_rw::<crate::DefaultEnvironment>(&contract),
+            (1, 0)
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_accesses_are_recorded_in_order() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        let key = scale::Encode::encode(&7u32);
+
+        // when
+        let _ = crate::get_contract_storage::<u32, u32>(&7);
+        crate::set_contract_storage(&7u32, &42u32);
+
+        // then
+        assert_eq!(
+            crate::test::recorded_storage_accesses(),
+            vec![
+                crate::test::StorageAccess::Read(key.clone()),
+                crate::test::StorageAccess::Write(key),
+            ]
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn expect_reads_and_writes_pass_on_match() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // when
+        crate::set_contract_storage(&7u32, &42u32);
+        let _ = crate::get_contract_storage::<u32, u32>(&7);
+
+        // then
+        crate::test::expect_reads(1);
+        crate::test::expect_writes(1);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn expect_writes_counts_writes_of_every_callee() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // when
+        crate::set_contract_storage(&7u32, &42u32);
+        crate::test::set_callee::<crate::DefaultEnvironment>(accounts.bob);
+        crate::set_contract_storage(&8u32, &42u32);
+        crate::clear_contract_storage(&8u32);
+
+        // then
+        crate::test::expect_writes(3);
+        crate::test::expect_reads(0);
+
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(
+    expected = "expected 2 storage writes, but 1 were performed (written keys: [[7, 0, 0, 0]])"
+)]
+fn expect_writes_reports_mismatch() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // when
+        crate::set_contract_storage(&7u32, &42u32);
+
+        // then
+        crate::test::expect_writes(2);
+
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn return_value_is_recorded() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        assert_eq!(crate::test::returned_value(), None);
+
+        // when
+        let result = std::panic::catch_unwind(|| {
+            crate::return_value(crate::ReturnFlags::empty(), &42u32)
+        });
+
+        // then
+        let expected = crate::test::ReturnedValue {
+            reverted: false,
+            data: scale::Encode::encode(&42u32),
+        };
+        let payload = result.expect_err("return_value must not return");
+        assert_eq!(
+            payload.downcast_ref::<crate::test::ReturnedValue>(),
+            Some(&expected)
+        );
+        assert_eq!(crate::test::returned_value(), Some(expected));
+
+        Ok(())
+    })
+}
+
+#[test]
+fn reverted_return_value_is_recorded() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // when
+        let _ = std::panic::catch_unwind(|| {
+            crate::return_value(crate::ReturnFlags::REVERT, &42u32)
+        });
+
+        // then
+        assert_eq!(
+            crate::test::returned_value(),
+            Some(crate::test::ReturnedValue {
+                reverted: true,
+                data: scale::Encode::encode(&42u32),
+            })
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_operations_charge_gas() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::set_storage_weights(2, 5);
+        crate::test::set_gas_limit(Some(7));
+
+        // when
+        crate::set_contract_storage(&7u32, &42u32);
+        let _ = crate::get_contract_storage::<u32, u32>(&7);
+
+        // then
+        assert_eq!(crate::test::gas_used(), 7);
+        crate::test::set_gas_limit(None);
+        crate::set_contract_storage(&7u32, &43u32);
+        assert_eq!(crate::test::gas_used(), 12);
+
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "out of gas")]
+fn exceeding_gas_limit_panics() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::set_gas_limit(Some(5));
+
+        // when
+        crate::set_contract_storage(&7u32, &42u32);
+
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn run_test_resets_gas() -> Result<()> {
+    // given
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        crate::test::set_storage_weights(100, 100);
+        crate::set_contract_storage(&7u32, &42u32);
+        assert_eq!(crate::test::gas_used(), 100);
+        crate::test::set_gas_limit(Some(15));
+
+        Ok(())
+    })?;
+
+    // when
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // then
+        assert_eq!(crate::test::gas_used(), 0);
+        crate::set_contract_storage(&7u32, &42u32);
+        crate::set_contract_storage(&7u32, &43u32);
+        assert_eq!(crate::test::gas_used(), 20);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn run_test_clears_max_value_len() -> Result<()> {
+    // given
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        crate::test::set_max_value_len(Some(1));
+
+        Ok(())
+    })?;
+
+    // when
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // then
+        crate::set_contract_storage(&7u32, &[0u8; 64]);
+        assert_eq!(
+            crate::get_contract_storage::<u32, [u8; 64]>(&7),
+            Ok(Some([0u8; 64]))
+        );
+
+        Ok(())
+    })
+}
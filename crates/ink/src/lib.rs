@@ -59,7 +59,14 @@ pub mod storage {
     pub use ink_storage::{
         Lazy,
         Mapping,
+        StorageArray,
+        StorageDeque,
+        StorageHeap,
+        StorageLinkedList,
+        StorageRingBuffer,
+        StorageSet,
         StorageVec,
+        StorageVecCapacityError,
     };
 }
 
@@ -0,0 +1,285 @@
+// Copyright (C) Use Ink (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simple fixed size array implementation built on top of [Mapping].
+//!
+//! # Note
+//!
+//! This array doesn't actually "own" any data.
+//! Instead it is just a simple wrapper around the contract storage facilities.
+
+use ink_primitives::Key;
+use ink_storage_traits::{
+    AutoKey,
+    Packed,
+    Storable,
+    StorableHint,
+    StorageKey,
+};
+use scale::EncodeLike;
+
+use crate::Mapping;
+
+/// An array of `N` values (elements) directly on contract storage.
+///
+/// Since its size is fixed, the array doesn't need to store a length. Slots which
+/// were never set are empty.
+///
+/// # Important
+///
+/// [StorageArray] requires its own pre-defined storage key where to store values. By
+/// default, the is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey). Specifying the storage key can be helpful for
+/// upgradeable contracts or you want to be resistant to future changes of storage key
+/// calculation strategy.
+///
+/// # Storage Layout
+///
+/// Given [StorageArray] under key `K`, the storage key `E` of the `N`th element is
+/// calculated as follows:
+///
+/// `E = scale::Encode((K, N))`
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageArray<V: Packed, const N: u32, KeyType: StorageKey = AutoKey> {
+    /// We use a [Mapping] to store all elements of the array.
+    /// Each element is living in storage under `&(KeyType::KEY, index)`.
+    elements: Mapping<u32, V, KeyType>,
+}
+
+impl<V, const N: u32, KeyType> Default for StorageArray<V, N, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, const N: u32, KeyType> Storable for StorageArray<V, N, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: scale::Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: scale::Input>(_input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Default::default())
+    }
+
+    #[inline]
+    fn encoded_size(&self) -> usize {
+        0
+    }
+}
+
+impl<V, const N: u32, Key, InnerKey> StorableHint<Key> for StorageArray<V, N, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageArray<V, N, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, const N: u32, KeyType> StorageKey for StorageArray<V, N, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, const N: u32, KeyType> StorageLayout for StorageArray<V, N, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+                scale_info::meta_type::<Self>(),
+            ))
+        }
+    }
+};
+
+impl<V, const N: u32, KeyType> StorageArray<V, N, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new `StorageArray`.
+    pub const fn new() -> Self {
+        Self {
+            elements: Mapping::new(),
+        }
+    }
+
+    /// Returns the number of slots of the array.
+    pub const fn len(&self) -> u32 {
+        N
+    }
+
+    /// Returns `true` if the array has no slots.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Access an element at given `index`.
+    ///
+    /// Returns `None` if the `index` is out of bounds or there was no value at the
+    /// `index`. Only the element at `index` is read from storage.
+    ///
+    /// # Panics
+    ///
+    /// * If encoding the element exceeds the static buffer size.
+    pub fn get(&self, index: u32) -> Option<V> {
+        if index >= N {
+            return None;
+        }
+
+        self.elements.get(index)
+    }
+
+    /// Set the `value` at given `index`.
+    ///
+    /// # Panics
+    ///
+    /// * If the index is out of bounds.
+    /// * If the value overgrows the static buffer size.
+    pub fn set<T>(&mut self, index: u32, value: &T) -> Option<u32>
+    where
+        T: Storable + EncodeLike<V>,
+    {
+        assert!(index < N);
+
+        self.elements.insert(index, value)
+    }
+
+    /// Delete the element at `index` (if any).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn clear_at(&mut self, index: u32) {
+        assert!(index < N);
+
+        self.elements.remove(index);
+    }
+}
+
+impl<V, const N: u32, KeyType> ::core::fmt::Debug for StorageArray<V, N, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageArray")
+            .field("key", &KeyType::KEY)
+            .field("len", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lazy::storage_rw,
+        traits::ManualKey,
+    };
+
+    #[test]
+    fn slots_are_independent() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageArray<u32, 4> = StorageArray::new();
+
+            array.set(1, &10);
+            array.set(3, &30);
+
+            assert_eq!(array.len(), 4);
+            assert_eq!(array.get(0), None);
+            assert_eq!(array.get(1), Some(10));
+            assert_eq!(array.get(2), None);
+            assert_eq!(array.get(3), Some(30));
+
+            array.clear_at(1);
+            assert_eq!(array.get(1), None);
+            assert_eq!(array.get(3), Some(30));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn get_out_of_bounds_returns_none() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            const BASE: u32 = 123;
+            let array: StorageArray<u32, 4, ManualKey<BASE>> = StorageArray::new();
+            // A value which happens to be stored right after the last slot.
+            ink_env::set_contract_storage(&(BASE, 4u32), &40u32);
+
+            assert_eq!(array.get(4), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds_panics() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            StorageArray::<u32, 4>::new().set(4, &40);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn get_loads_only_its_slot() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageArray<u32, 4> = StorageArray::new();
+            for index in 0..4 {
+                array.set(index, &index);
+            }
+            let (reads_before, _) = storage_rw();
+
+            assert_eq!(array.get(2), Some(2));
+
+            let (reads_after, _) = storage_rw();
+            assert_eq!(reads_after - reads_before, 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
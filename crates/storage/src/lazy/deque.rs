@@ -0,0 +1,388 @@
+// Copyright (C) Use Ink (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simple double-ended queue implementation built on top of [Mapping].
+//!
+//! # Note
+//!
+//! This queue doesn't actually "own" any data.
+//! Instead it is just a simple wrapper around the contract storage facilities.
+
+use ink_primitives::Key;
+use ink_storage_traits::{
+    AutoKey,
+    Packed,
+    Storable,
+    StorableHint,
+    StorageKey,
+};
+use scale::EncodeLike;
+
+use crate::{
+    Lazy,
+    Mapping,
+};
+
+/// A double-ended queue of values (elements) directly on contract storage.
+///
+/// Elements can be pushed to and popped from both ends of the queue.
+///
+/// # Important
+///
+/// [StorageDeque] requires its own pre-defined storage key where to store values. By
+/// default, the is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey). Specifying the storage key can be helpful for
+/// upgradeable contracts or you want to be resistant to future changes of storage key
+/// calculation strategy.
+///
+/// # Storage Layout
+///
+/// At given [StorageKey] `K`, the `(head, tail)` indices of the [StorageDeque] are
+/// hold. The elements live in a circular slot space of 2 ^ 32 slots, starting at slot
+/// `head` and ending before slot `tail`. Pushing to the front decrements `head` and
+/// pushing to the back increments `tail`, both wrapping around on overflow.
+///
+/// Given [StorageDeque] under key `K`, the storage key `E` of the element in slot `N` is
+/// calculated as follows:
+///
+/// `E = scale::Encode((K, N))`
+///
+/// Slots are cleared from storage as soon as their element is popped.
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageDeque<V: Packed, KeyType: StorageKey = AutoKey> {
+    /// The slot of the first element and the slot after the last element.
+    indices: Lazy<(u32, u32), KeyType>,
+    /// We use a [Mapping] to store all elements of the queue.
+    /// Each element is living in storage under `&(KeyType::KEY, slot)`.
+    elements: Mapping<u32, V, KeyType>,
+}
+
+impl<V, KeyType> Default for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> Storable for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: scale::Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: scale::Input>(_input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Default::default())
+    }
+
+    #[inline]
+    fn encoded_size(&self) -> usize {
+        0
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageDeque<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageDeque<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageDeque<V, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+                scale_info::meta_type::<Self>(),
+            ))
+        }
+    }
+};
+
+impl<V, KeyType> StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageDeque`.
+    pub const fn new() -> Self {
+        Self {
+            indices: Lazy::new(),
+            elements: Mapping::new(),
+        }
+    }
+
+    /// Returns the `(head, tail)` slot indices.
+    fn indices(&self) -> (u32, u32) {
+        self.indices.get().unwrap_or_default()
+    }
+
+    /// Returns the number of elements in the queue, also referred to as its length.
+    pub fn len(&self) -> u32 {
+        let (head, tail) = self.indices();
+        tail.wrapping_sub(head)
+    }
+
+    /// Returns `true` if the queue contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends an element to the back of the queue.
+    ///
+    /// # Panics
+    ///
+    /// * If the queue is at capacity (max. of 2 ^ 32 - 1 elements).
+    /// * If the value overgrows the static buffer size.
+    pub fn push_back<T>(&mut self, value: &T)
+    where
+        T: Storable + EncodeLike<V>,
+    {
+        let (head, tail) = self.indices();
+        assert!(
+            tail.wrapping_sub(head) < u32::MAX,
+            "StorageDeque is at capacity"
+        );
+
+        self.elements.insert(tail, value);
+        self.indices.set(&(head, tail.wrapping_add(1)));
+    }
+
+    /// Prepends an element to the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// * If the queue is at capacity (max. of 2 ^ 32 - 1 elements).
+    /// * If the value overgrows the static buffer size.
+    pub fn push_front<T>(&mut self, value: &T)
+    where
+        T: Storable + EncodeLike<V>,
+    {
+        let (head, tail) = self.indices();
+        assert!(
+            tail.wrapping_sub(head) < u32::MAX,
+            "StorageDeque is at capacity"
+        );
+
+        let head = head.wrapping_sub(1);
+        self.elements.insert(head, value);
+        self.indices.set(&(head, tail));
+    }
+
+    /// Clears the last element from the storage and returns it.
+    ///
+    /// Returns `None` if the queue is empty.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn pop_back(&mut self) -> Option<V> {
+        let (head, tail) = self.indices();
+        if head == tail {
+            return None;
+        }
+
+        let tail = tail.wrapping_sub(1);
+        self.indices.set(&(head, tail));
+
+        self.elements.take(tail)
+    }
+
+    /// Clears the first element from the storage and returns it.
+    ///
+    /// Returns `None` if the queue is empty.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn pop_front(&mut self) -> Option<V> {
+        let (head, tail) = self.indices();
+        if head == tail {
+            return None;
+        }
+
+        self.indices.set(&(head.wrapping_add(1), tail));
+
+        self.elements.take(head)
+    }
+
+    /// Get a copy of the first element without removing it from storage.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn front(&self) -> Option<V> {
+        let (head, tail) = self.indices();
+        if head == tail {
+            return None;
+        }
+
+        self.elements.get(head)
+    }
+
+    /// Get a copy of the last element without removing it from storage.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn back(&self) -> Option<V> {
+        let (head, tail) = self.indices();
+        if head == tail {
+            return None;
+        }
+
+        self.elements.get(tail.wrapping_sub(1))
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageDeque")
+            .field("key", &KeyType::KEY)
+            .field("indices", &self.indices)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn empty_deque_works_as_expected() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut deque: StorageDeque<u8> = StorageDeque::new();
+
+            assert_eq!(deque.pop_front(), None);
+            assert_eq!(deque.pop_back(), None);
+            assert_eq!(deque.front(), None);
+            assert_eq!(deque.back(), None);
+            assert_eq!(deque.len(), 0);
+            assert!(deque.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn fifo_and_lifo_from_both_ends_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut deque: StorageDeque<u8> = StorageDeque::new();
+
+            deque.push_back(&1);
+            deque.push_back(&2);
+            deque.push_front(&0);
+            deque.push_front(&255);
+            assert_eq!(deque.len(), 4);
+            assert_eq!(deque.front(), Some(255));
+            assert_eq!(deque.back(), Some(2));
+
+            // LIFO from the back
+            assert_eq!(deque.pop_back(), Some(2));
+            // FIFO from the front
+            assert_eq!(deque.pop_front(), Some(255));
+            assert_eq!(deque.pop_front(), Some(0));
+
+            deque.push_front(&7);
+            assert_eq!(deque.pop_back(), Some(1));
+            assert_eq!(deque.pop_back(), Some(7));
+            assert_eq!(deque.pop_back(), None);
+            assert!(deque.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn pop_clears_exactly_its_slot() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            const BASE: u32 = 123;
+            let mut deque: StorageDeque<u8, ManualKey<BASE>> = StorageDeque::new();
+
+            deque.push_back(&1);
+            deque.push_front(&0);
+            assert_eq!(
+                ink_env::contains_contract_storage(&(BASE, u32::MAX)),
+                Some(1)
+            );
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, 0u32)), Some(1));
+
+            assert_eq!(deque.pop_front(), Some(0));
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, u32::MAX)), None);
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, 0u32)), Some(1));
+
+            assert_eq!(deque.pop_back(), Some(1));
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, 0u32)), None);
+            assert_eq!(
+                ink_env::get_contract_storage::<_, (u32, u32)>(&BASE),
+                Ok(Some((0, 0)))
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn push_and_pop_work_for_two_deques_with_same_manual_key() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut deque: StorageDeque<u8, ManualKey<123>> = StorageDeque::new();
+            deque.push_front(&42);
+
+            let mut deque2: StorageDeque<u8, ManualKey<123>> = StorageDeque::new();
+            assert_eq!(deque2.pop_back(), Some(42));
+            assert!(deque.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
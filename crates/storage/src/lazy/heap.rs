@@ -0,0 +1,355 @@
+// Copyright (C) Use Ink (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simple binary max-heap implementation built on top of [Mapping].
+//!
+//! # Note
+//!
+//! This heap doesn't actually "own" any data.
+//! Instead it is just a simple wrapper around the contract storage facilities.
+
+use ink_primitives::Key;
+use ink_storage_traits::{
+    AutoKey,
+    Packed,
+    Storable,
+    StorableHint,
+    StorageKey,
+};
+
+use crate::{
+    Lazy,
+    Mapping,
+};
+
+/// A priority queue of values (elements) directly on contract storage, implemented
+/// as a binary max-heap.
+///
+/// Pushing and popping only loads the elements on a single path through the heap,
+/// which are `O(log n)` elements.
+///
+/// # Important
+///
+/// [StorageHeap] requires its own pre-defined storage key where to store values. By
+/// default, the is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey). Specifying the storage key can be helpful for
+/// upgradeable contracts or you want to be resistant to future changes of storage key
+/// calculation strategy.
+///
+/// # Storage Layout
+///
+/// At given [StorageKey] `K`, the length of the [StorageHeap] is hold. Its elements
+/// are stored in level order: the children of the element at index `N` are stored at
+/// the indices `2N + 1` and `2N + 2`.
+///
+/// Given [StorageHeap] under key `K`, the storage key `E` of the `N`th element is
+/// calculated as follows:
+///
+/// `E = scale::Encode((K, N))`
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageHeap<V: Packed, KeyType: StorageKey = AutoKey> {
+    /// The number of elements stored on-chain.
+    len: Lazy<u32, KeyType>,
+    /// We use a [Mapping] to store all elements of the heap.
+    /// Each element is living in storage under `&(KeyType::KEY, index)`.
+    elements: Mapping<u32, V, KeyType>,
+}
+
+impl<V, KeyType> Default for StorageHeap<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> Storable for StorageHeap<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: scale::Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: scale::Input>(_input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Default::default())
+    }
+
+    #[inline]
+    fn encoded_size(&self) -> usize {
+        0
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageHeap<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageHeap<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageHeap<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageHeap<V, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+                scale_info::meta_type::<Self>(),
+            ))
+        }
+    }
+};
+
+impl<V, KeyType> StorageHeap<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageHeap`.
+    pub const fn new() -> Self {
+        Self {
+            len: Lazy::new(),
+            elements: Mapping::new(),
+        }
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> u32 {
+        self.len.get().unwrap_or(u32::MIN)
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a copy of the greatest element without removing it from storage.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn peek(&self) -> Option<V> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.elements.get(0)
+    }
+}
+
+impl<V, KeyType> StorageHeap<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    /// Pushes an element onto the heap.
+    ///
+    /// # Panics
+    ///
+    /// * If the heap is at capacity (max. of 2 ^ 32 elements).
+    /// * If the value overgrows the static buffer size.
+    pub fn push(&mut self, value: &V) {
+        let len = self.len();
+        self.len.set(&len.checked_add(1).unwrap());
+
+        // Move smaller parents down until the hole is at the right place for `value`.
+        let mut hole = len;
+        while hole > 0 {
+            let parent = (hole - 1) / 2;
+            let parent_value = self.elements.get(parent).unwrap();
+            if parent_value >= *value {
+                break;
+            }
+            self.elements.insert(hole, &parent_value);
+            hole = parent;
+        }
+
+        self.elements.insert(hole, value);
+    }
+
+    /// Clears the greatest element from the storage and returns it.
+    ///
+    /// Returns `None` if the heap is empty.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn pop(&mut self) -> Option<V> {
+        let len = self.len().checked_sub(1)?;
+        self.len.set(&len);
+
+        let last = self.elements.take(len).unwrap();
+        if len == 0 {
+            return Some(last);
+        }
+        let greatest = self.elements.get(0).unwrap();
+
+        // Move greater children up until the hole is at the right place for `last`.
+        let mut hole = 0;
+        while let Some(mut child) = hole
+            .checked_mul(2)
+            .and_then(|index| index.checked_add(1))
+            .filter(|index| *index < len)
+        {
+            let mut child_value = self.elements.get(child).unwrap();
+            if child + 1 < len {
+                let sibling_value = self.elements.get(child + 1).unwrap();
+                if sibling_value > child_value {
+                    child += 1;
+                    child_value = sibling_value;
+                }
+            }
+            if child_value <= last {
+                break;
+            }
+            self.elements.insert(hole, &child_value);
+            hole = child;
+        }
+
+        self.elements.insert(hole, &last);
+        Some(greatest)
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageHeap<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageHeap")
+            .field("key", &KeyType::KEY)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lazy::storage_rw,
+        traits::ManualKey,
+    };
+
+    #[test]
+    fn empty_heap_works_as_expected() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut heap: StorageHeap<u8> = StorageHeap::new();
+
+            assert_eq!(heap.len(), 0);
+            assert!(heap.is_empty());
+            assert_eq!(heap.peek(), None);
+            assert_eq!(heap.pop(), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn pop_returns_elements_in_descending_order() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut heap: StorageHeap<u8> = StorageHeap::new();
+            let elements = [7, 3, 9, 1, 8, 2, 9, 5, 0, 6, 4];
+
+            for element in elements {
+                heap.push(&element);
+            }
+            assert_eq!(heap.len(), elements.len() as u32);
+            assert_eq!(heap.peek(), Some(9));
+
+            let mut popped = Vec::new();
+            while let Some(element) = heap.pop() {
+                popped.push(element);
+            }
+            let mut expected = elements.to_vec();
+            expected.sort_by(|a, b| b.cmp(a));
+            assert_eq!(popped, expected);
+            assert!(heap.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn pop_clears_the_tail_slot() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            const BASE: u32 = 123;
+            let mut heap: StorageHeap<u8, ManualKey<BASE>> = StorageHeap::new();
+            heap.push(&1);
+            heap.push(&2);
+
+            assert_eq!(heap.pop(), Some(2));
+
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, 1u32)), None);
+            assert_eq!(ink_env::get_contract_storage(&(BASE, 0u32)), Ok(Some(1u8)));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn pop_loads_a_single_path() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut heap: StorageHeap<u32> = StorageHeap::new();
+            for element in (0..64).map(|i| (i * 37) % 64) {
+                heap.push(&element);
+            }
+
+            while !heap.is_empty() {
+                let depth = heap.len().ilog2();
+                let (reads_before, _) = storage_rw();
+
+                heap.pop();
+
+                let (reads_after, _) = storage_rw();
+                // The length, the root and both children on each level of the path.
+                assert!(reads_after - reads_before <= 2 + 2 * depth as usize);
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,470 @@
+// Copyright (C) Use Ink (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simple doubly-linked list implementation built on top of [Mapping].
+//!
+//! # Note
+//!
+//! This list doesn't actually "own" any data.
+//! Instead it is just a simple wrapper around the contract storage facilities.
+
+use ink_primitives::Key;
+use ink_storage_traits::{
+    AutoKey,
+    Packed,
+    Storable,
+    StorableHint,
+    StorageKey,
+};
+use scale::EncodeLike;
+
+use crate::{
+    Lazy,
+    Mapping,
+};
+
+/// A doubly-linked list of values (elements) directly on contract storage.
+///
+/// Every element is addressed by a stable handle, which is returned when the element
+/// is pushed. Removing an element by its handle only touches the element itself and
+/// its direct neighbours, regardless of its position in the list.
+///
+/// # Important
+///
+/// [StorageLinkedList] requires its own pre-defined storage key where to store values.
+/// By default, the is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can specify a
+/// storage key using [`ManualKey`](crate::traits::ManualKey). Specifying the storage key
+/// can be helpful for upgradeable contracts or you want to be resistant to future changes
+/// of storage key calculation strategy.
+///
+/// # Storage Layout
+///
+/// At given [StorageKey] `K`, the handles of the first and last element, the number of
+/// elements and the next free handle of the [StorageLinkedList] are hold.
+///
+/// Given [StorageLinkedList] under key `K`, the storage key `E` of the element with
+/// handle `N` and the storage key `L` of its links are calculated as follows:
+///
+/// `E = scale::Encode((K, N))`
+///
+/// `L = scale::Encode((K, (N, 0u8)))`
+///
+/// The links hold the handles of the previous and next element. They are stored apart
+/// from the element, so relinking a neighbour never reads or re-encodes its value.
+/// Handles are never reused, so a handle of a removed element stays invalid.
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageLinkedList<V: Packed, KeyType: StorageKey = AutoKey> {
+    /// The list bookkeeping.
+    header: Lazy<Header, KeyType>,
+    /// We use a [Mapping] to store all elements of the list.
+    /// Each element is living in storage under `&(KeyType::KEY, handle)`.
+    values: Mapping<u32, V, KeyType>,
+    /// We use a second [Mapping] to store the links of all elements.
+    /// The links of each element are living in storage under
+    /// `&(KeyType::KEY, (handle, LINKS))`.
+    links: Mapping<(u32, u8), Links, KeyType>,
+}
+
+/// The suffix of the storage keys of [Links], setting them apart from the elements.
+const LINKS: u8 = 0;
+
+/// The bookkeeping of a [StorageLinkedList].
+#[derive(Default, Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header {
+    /// The handle of the first element.
+    head: Option<u32>,
+    /// The handle of the last element.
+    tail: Option<u32>,
+    /// The number of elements.
+    len: u32,
+    /// The handle assigned to the next pushed element.
+    next_handle: u32,
+}
+
+/// The links of an element of a [StorageLinkedList].
+#[derive(Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Links {
+    /// The handle of the previous element.
+    prev: Option<u32>,
+    /// The handle of the next element.
+    next: Option<u32>,
+}
+
+impl<V, KeyType> Default for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> Storable for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: scale::Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: scale::Input>(_input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Default::default())
+    }
+
+    #[inline]
+    fn encoded_size(&self) -> usize {
+        0
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageLinkedList<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageLinkedList<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageLinkedList<V, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+                scale_info::meta_type::<Self>(),
+            ))
+        }
+    }
+};
+
+impl<V, KeyType> StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageLinkedList`.
+    pub const fn new() -> Self {
+        Self {
+            header: Lazy::new(),
+            values: Mapping::new(),
+            links: Mapping::new(),
+        }
+    }
+
+    /// Returns the list bookkeeping.
+    fn header(&self) -> Header {
+        self.header.get().unwrap_or_default()
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> u32 {
+        self.header().len
+    }
+
+    /// Returns `true` if the list contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Allocates the handle for a new element.
+    ///
+    /// # Panics
+    ///
+    /// If all 2 ^ 32 handles were already used.
+    fn next_handle(header: &mut Header) -> u32 {
+        let handle = header.next_handle;
+        header.next_handle = handle
+            .checked_add(1)
+            .expect("StorageLinkedList is out of handles");
+        handle
+    }
+
+    /// Returns the links of the element with the given `handle`.
+    fn links(&self, handle: u32) -> Links {
+        self.links
+            .get((handle, LINKS))
+            .expect("linked element must exist")
+    }
+
+    /// Sets the `next` link of the element with the given `handle`.
+    ///
+    /// Only the links are read and written, the element itself is not touched.
+    fn set_next(&mut self, handle: u32, next: Option<u32>) {
+        let links = Links {
+            next,
+            ..self.links(handle)
+        };
+        self.links.insert((handle, LINKS), &links);
+    }
+
+    /// Sets the `prev` link of the element with the given `handle`.
+    ///
+    /// Only the links are read and written, the element itself is not touched.
+    fn set_prev(&mut self, handle: u32, prev: Option<u32>) {
+        let links = Links {
+            prev,
+            ..self.links(handle)
+        };
+        self.links.insert((handle, LINKS), &links);
+    }
+
+    /// Appends an element to the back of the list and returns its handle.
+    ///
+    /// # Panics
+    ///
+    /// * If all 2 ^ 32 handles were already used.
+    /// * If the value overgrows the static buffer size.
+    pub fn push_back<T>(&mut self, value: &T) -> u32
+    where
+        T: Storable + EncodeLike<V>,
+    {
+        let mut header = self.header();
+        let handle = Self::next_handle(&mut header);
+        header.len += 1;
+
+        self.values.insert(handle, value);
+        self.links.insert(
+            (handle, LINKS),
+            &Links {
+                prev: header.tail,
+                next: None,
+            },
+        );
+        match header.tail {
+            Some(tail) => self.set_next(tail, Some(handle)),
+            None => header.head = Some(handle),
+        }
+        header.tail = Some(handle);
+        self.header.set(&header);
+
+        handle
+    }
+
+    /// Prepends an element to the front of the list and returns its handle.
+    ///
+    /// # Panics
+    ///
+    /// * If all 2 ^ 32 handles were already used.
+    /// * If the value overgrows the static buffer size.
+    pub fn push_front<T>(&mut self, value: &T) -> u32
+    where
+        T: Storable + EncodeLike<V>,
+    {
+        let mut header = self.header();
+        let handle = Self::next_handle(&mut header);
+        header.len += 1;
+
+        self.values.insert(handle, value);
+        self.links.insert(
+            (handle, LINKS),
+            &Links {
+                prev: None,
+                next: header.head,
+            },
+        );
+        match header.head {
+            Some(head) => self.set_prev(head, Some(handle)),
+            None => header.tail = Some(handle),
+        }
+        header.head = Some(handle);
+        self.header.set(&header);
+
+        handle
+    }
+
+    /// Clears the element with the given `handle` from the storage and returns it.
+    ///
+    /// Returns `None` if there is no element with the given `handle`.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn remove(&mut self, handle: u32) -> Option<V> {
+        let value = self.values.take(handle)?;
+        let links = self
+            .links
+            .take((handle, LINKS))
+            .expect("linked element must exist");
+        let mut header = self.header();
+
+        match links.prev {
+            Some(prev) => self.set_next(prev, links.next),
+            None => header.head = links.next,
+        }
+        match links.next {
+            Some(next) => self.set_prev(next, links.prev),
+            None => header.tail = links.prev,
+        }
+        header.len -= 1;
+        self.header.set(&header);
+
+        Some(value)
+    }
+
+    /// Returns an iterator over the elements of the list, from the front to the back.
+    ///
+    /// Each element and its links are only read from storage once the iterator
+    /// advances to it.
+    ///
+    /// # Panics
+    ///
+    /// * If encoding an element exceeds the static buffer size.
+    pub fn iter(&self) -> impl Iterator<Item = V> + '_ {
+        let mut cursor = self.header().head;
+        core::iter::from_fn(move || {
+            let handle = cursor?;
+            cursor = self.links(handle).next;
+            Some(self.values.get(handle).expect("linked element must exist"))
+        })
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageLinkedList")
+            .field("key", &KeyType::KEY)
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lazy::storage_rw,
+        traits::ManualKey,
+    };
+
+    #[test]
+    fn empty_list_works_as_expected() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut list: StorageLinkedList<u8> = StorageLinkedList::new();
+
+            assert_eq!(list.len(), 0);
+            assert!(list.is_empty());
+            assert_eq!(list.iter().next(), None);
+            assert_eq!(list.remove(0), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn push_to_both_ends_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut list: StorageLinkedList<u8> = StorageLinkedList::new();
+
+            list.push_back(&1);
+            list.push_back(&2);
+            list.push_front(&0);
+
+            assert_eq!(list.len(), 3);
+            assert_eq!(list.iter().collect::<Vec<_>>(), [0, 1, 2]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_relinks_neighbours() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            const BASE: u32 = 123;
+            let mut list: StorageLinkedList<u8, ManualKey<BASE>> =
+                StorageLinkedList::new();
+            let first = list.push_back(&1);
+            let middle = list.push_back(&2);
+            let last = list.push_back(&3);
+
+            assert_eq!(list.remove(middle), Some(2));
+            assert_eq!(list.remove(middle), None);
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, middle)), None);
+            assert_eq!(
+                ink_env::contains_contract_storage(&(BASE, (middle, LINKS))),
+                None
+            );
+
+            // Read the links back through a fresh handle to the same storage.
+            let mut list: StorageLinkedList<u8, ManualKey<BASE>> =
+                StorageLinkedList::new();
+            assert_eq!(list.len(), 2);
+            assert_eq!(list.iter().collect::<Vec<_>>(), [1, 3]);
+
+            assert_eq!(list.remove(last), Some(3));
+            assert_eq!(list.iter().collect::<Vec<_>>(), [1]);
+            list.push_back(&4);
+            assert_eq!(list.remove(first), Some(1));
+            assert_eq!(list.iter().collect::<Vec<_>>(), [4]);
+            list.push_front(&5);
+            assert_eq!(list.iter().collect::<Vec<_>>(), [5, 4]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_reads_only_links_of_neighbours() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut list: StorageLinkedList<u8> = StorageLinkedList::new();
+            list.push_back(&1);
+            let middle = list.push_back(&2);
+            list.push_back(&3);
+
+            let (reads_before, _) = storage_rw();
+            assert_eq!(list.remove(middle), Some(2));
+            let (reads_after, _) = storage_rw();
+
+            // The header and the links of both neighbours, but none of their values.
+            assert_eq!(reads_after - reads_before, 3);
+            assert_eq!(list.iter().collect::<Vec<_>>(), [1, 3]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
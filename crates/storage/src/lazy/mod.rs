@@ -18,12 +18,29 @@
 //! These low-level collections are not aware of the elements they manage thus
 //! extra care has to be taken when operating directly on them.
 
+mod array;
+mod deque;
+mod heap;
+mod linked_list;
 mod mapping;
+mod ring_buffer;
+mod set;
 mod vec;
 
 #[doc(inline)]
 pub use self::mapping::Mapping;
-pub use self::vec::StorageVec;
+pub use self::{
+    array::StorageArray,
+    deque::StorageDeque,
+    heap::StorageHeap,
+    linked_list::StorageLinkedList,
+    ring_buffer::StorageRingBuffer,
+    set::StorageSet,
+    vec::{
+        StorageVec,
+        StorageVecCapacityError,
+    },
+};
 
 use crate::traits::{
     AutoKey,
@@ -200,6 +217,64 @@ where
 
         Ok(())
     }
+
+    /// Reads the `value` from the contract storage, computing and writing it with `f`
+    /// if it doesn't exist yet.
+    ///
+    /// This is meant for values which are computed once and never change afterwards,
+    /// such as precomputed tables: `f` only runs on the first call, every later call
+    /// loads the persisted value instead.
+    ///
+    /// # Panics
+    ///
+    /// Traps if the encoded `value` doesn't fit into the static buffer.
+    pub fn get_or_init<F>(&mut self, f: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        match self.get() {
+            Some(value) => value,
+            None => {
+                let value = f();
+                self.set(&value);
+                value
+            }
+        }
+    }
+}
+
+impl<V, KeyType> Lazy<V, KeyType>
+where
+    V: Storable + Clone,
+    KeyType: StorageKey,
+{
+    /// Replaces the `value` in the contract storage with the one computed by `f`
+    /// from the current value.
+    ///
+    /// If `f` returns `None`, the value is removed from the contract storage. Nothing
+    /// is written if there was no value to begin with.
+    ///
+    /// Returns the old and the new value.
+    ///
+    /// # Panics
+    ///
+    /// Traps if the encoded `value` doesn't fit into the static buffer.
+    pub fn fetch_update<F>(&mut self, f: F) -> (Option<V>, Option<V>)
+    where
+        F: FnOnce(Option<V>) -> Option<V>,
+    {
+        let old = self.get();
+        let new = f(old.clone());
+        match (&old, &new) {
+            (_, Some(value)) => self.set(value),
+            (Some(_), None) => {
+                ink_env::clear_contract_storage(&KeyType::KEY);
+            }
+            (None, None) => {}
+        }
+
+        (old, new)
+    }
 }
 
 impl<V, KeyType> Lazy<V, KeyType>
@@ -218,6 +293,51 @@ where
     }
 }
 
+impl<V, KeyType> Lazy<V, KeyType>
+where
+    V: Storable + PartialEq,
+    KeyType: StorageKey,
+{
+    /// Writes `new` to the contract storage only if the stored value equals
+    /// `expected`.
+    ///
+    /// Use `None` as `expected` to only write if no value exists yet.
+    ///
+    /// Returns `Err` containing the actually stored value if it didn't match
+    /// `expected`. In this case nothing is written.
+    ///
+    /// # Panics
+    ///
+    /// Traps if the encoded `value` doesn't fit into the static buffer.
+    pub fn compare_and_swap(
+        &mut self,
+        expected: Option<&V>,
+        new: &V,
+    ) -> Result<(), Option<V>> {
+        let current = self.get();
+        if current.as_ref() != expected {
+            return Err(current);
+        }
+
+        self.set(new);
+        Ok(())
+    }
+
+    /// Writes `new` to the contract storage only if the stored value equals
+    /// `expected`.
+    ///
+    /// Returns `true` if `new` was written. This is the same as
+    /// [`compare_and_swap`](Self::compare_and_swap), but discards the mismatching
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// Traps if the encoded `value` doesn't fit into the static buffer.
+    pub fn compare_and_set(&mut self, expected: Option<&V>, new: &V) -> bool {
+        self.compare_and_swap(expected, new).is_ok()
+    }
+}
+
 impl<V, KeyType> Storable for Lazy<V, KeyType>
 where
     KeyType: StorageKey,
@@ -277,6 +397,13 @@ const _: () = {
     }
 };
 
+/// Returns the number of storage reads and writes of the callee so far.
+#[cfg(test)]
+pub(crate) fn storage_rw() -> (usize, usize) {
+    let contract = ink_env::test::callee::<ink_env::DefaultEnvironment>();
+    ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(&contract)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,6 +446,136 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn get_or_init_computes_value_once() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u32> = Lazy::new();
+            let mut calls = 0;
+
+            for _ in 0..3 {
+                let value = storage.get_or_init(|| {
+                    calls += 1;
+                    42
+                });
+                assert_eq!(value, 42);
+            }
+
+            assert_eq!(calls, 1);
+            assert_eq!(storage.get(), Some(42));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn compare_and_swap_works_for_matching_value() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+            storage.set(&1);
+
+            assert_eq!(storage.compare_and_swap(Some(&1), &2), Ok(()));
+            assert_eq!(storage.get(), Some(2));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn compare_and_swap_fails_for_mismatching_value() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+            storage.set(&1);
+
+            assert_eq!(storage.compare_and_swap(Some(&3), &2), Err(Some(1)));
+            assert_eq!(storage.compare_and_swap(None, &2), Err(Some(1)));
+            assert_eq!(storage.get(), Some(1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn compare_and_swap_works_for_empty_cell() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+
+            assert_eq!(storage.compare_and_swap(Some(&1), &2), Err(None));
+            assert_eq!(storage.compare_and_swap(None, &2), Ok(()));
+            assert_eq!(storage.get(), Some(2));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn fetch_update_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+            storage.set(&1);
+
+            let result = storage.fetch_update(|value| value.map(|v| v + 1));
+
+            assert_eq!(result, (Some(1), Some(2)));
+            assert_eq!(storage.get(), Some(2));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn fetch_update_to_none_clears_value() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+            storage.set(&1);
+
+            let result = storage.fetch_update(|_| None);
+
+            assert_eq!(result, (Some(1), None));
+            assert_eq!(storage.get(), None);
+            assert_eq!(ink_env::contains_contract_storage(&storage.key()), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn fetch_update_from_none_to_none_does_not_write() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+            let (_, writes_before) = storage_rw();
+            assert_eq!(storage.fetch_update(|_| None), (None, None));
+            assert_eq!(storage_rw().1, writes_before);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn compare_and_set_writes_only_on_match() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+            storage.set(&1);
+            let (_, writes_before) = storage_rw();
+            assert!(!storage.compare_and_set(Some(&3), &2));
+            assert_eq!(storage_rw().1, writes_before);
+            assert_eq!(storage.get(), Some(1));
+
+            assert!(storage.compare_and_set(Some(&1), &2));
+            assert_eq!(storage_rw().1, writes_before + 1);
+            assert_eq!(storage.get(), Some(2));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn gets_returns_none_if_no_value_was_set() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -0,0 +1,377 @@
+// Copyright (C) Use Ink (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simple fixed capacity ring buffer implementation built on top of [Mapping].
+//!
+//! # Note
+//!
+//! This ring buffer doesn't actually "own" any data.
+//! Instead it is just a simple wrapper around the contract storage facilities.
+
+use ink_primitives::Key;
+use ink_storage_traits::{
+    AutoKey,
+    Packed,
+    Storable,
+    StorableHint,
+    StorageKey,
+};
+use scale::EncodeLike;
+
+use crate::{
+    Lazy,
+    Mapping,
+};
+
+/// A ring buffer of at most `CAPACITY` values (elements) directly on contract storage.
+///
+/// Once the ring buffer is full, pushing a new element overwrites the oldest one.
+/// This makes it a good fit for bounded logs, such as the last `N` events.
+///
+/// # Important
+///
+/// [StorageRingBuffer] requires its own pre-defined storage key where to store values.
+/// By default, the is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can specify a
+/// storage key using [`ManualKey`](crate::traits::ManualKey). Specifying the storage key
+/// can be helpful for upgradeable contracts or you want to be resistant to future changes
+/// of storage key calculation strategy.
+///
+/// # Storage Layout
+///
+/// At given [StorageKey] `K`, the `(head, len)` of the [StorageRingBuffer] is hold,
+/// where `head` is the slot of the oldest element. The elements live in the slots
+/// `0..CAPACITY`, wrapping around at `CAPACITY`.
+///
+/// Given [StorageRingBuffer] under key `K`, the storage key `E` of the element in slot
+/// `N` is calculated as follows:
+///
+/// `E = scale::Encode((K, N))`
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageRingBuffer<
+    V: Packed,
+    const CAPACITY: u32,
+    KeyType: StorageKey = AutoKey,
+> {
+    /// The slot of the oldest element and the number of elements.
+    header: Lazy<(u32, u32), KeyType>,
+    /// We use a [Mapping] to store all elements of the ring buffer.
+    /// Each element is living in storage under `&(KeyType::KEY, slot)`.
+    elements: Mapping<u32, V, KeyType>,
+}
+
+impl<V, const CAPACITY: u32, KeyType> Default for StorageRingBuffer<V, CAPACITY, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, const CAPACITY: u32, KeyType> Storable for StorageRingBuffer<V, CAPACITY, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: scale::Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: scale::Input>(_input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Default::default())
+    }
+
+    #[inline]
+    fn encoded_size(&self) -> usize {
+        0
+    }
+}
+
+impl<V, const CAPACITY: u32, Key, InnerKey> StorableHint<Key>
+    for StorageRingBuffer<V, CAPACITY, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageRingBuffer<V, CAPACITY, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, const CAPACITY: u32, KeyType> StorageKey
+    for StorageRingBuffer<V, CAPACITY, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, const CAPACITY: u32, KeyType> StorageLayout
+        for StorageRingBuffer<V, CAPACITY, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+                scale_info::meta_type::<Self>(),
+            ))
+        }
+    }
+};
+
+impl<V, const CAPACITY: u32, KeyType> StorageRingBuffer<V, CAPACITY, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Fails the build if `CAPACITY` is zero.
+    const NON_ZERO_CAPACITY: () = assert!(
+        CAPACITY > 0,
+        "StorageRingBuffer requires a non-zero CAPACITY"
+    );
+
+    /// Creates a new empty `StorageRingBuffer`.
+    ///
+    /// Using a `CAPACITY` of zero is a compile time error.
+    pub const fn new() -> Self {
+        let () = Self::NON_ZERO_CAPACITY;
+        Self {
+            header: Lazy::new(),
+            elements: Mapping::new(),
+        }
+    }
+
+    /// Returns the `(head, len)` header.
+    fn header(&self) -> (u32, u32) {
+        self.header.get().unwrap_or_default()
+    }
+
+    /// Returns the maximum number of elements the ring buffer can hold.
+    pub const fn capacity(&self) -> u32 {
+        CAPACITY
+    }
+
+    /// Returns the number of elements in the ring buffer.
+    pub fn len(&self) -> u32 {
+        self.header().1
+    }
+
+    /// Returns `true` if the ring buffer contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the slot of the element at `index`, counted from the oldest element.
+    fn slot(head: u32, index: u32) -> u32 {
+        ((head as u64 + index as u64) % CAPACITY as u64) as u32
+    }
+
+    /// Appends an element to the ring buffer.
+    ///
+    /// If the ring buffer is full, the oldest element is overwritten and returned.
+    /// Either way, only the slot of the new element and the `(head, len)` header are
+    /// written to storage.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn push<T>(&mut self, value: &T) -> Option<V>
+    where
+        T: Storable + EncodeLike<V>,
+    {
+        let (head, len) = self.header();
+        if len < CAPACITY {
+            self.elements.insert(Self::slot(head, len), value);
+            self.header.set(&(head, len + 1));
+            return None;
+        }
+
+        let evicted = self.elements.get(head);
+        self.elements.insert(head, value);
+        self.header.set(&(Self::slot(head, 1), len));
+        evicted
+    }
+
+    /// Access an element at given `index`, counted from the oldest element.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// * If encoding the element exceeds the static buffer size.
+    pub fn get(&self, index: u32) -> Option<V> {
+        let (head, len) = self.header();
+        if index >= len {
+            return None;
+        }
+
+        self.elements.get(Self::slot(head, index))
+    }
+
+    /// Returns an iterator over the elements of the ring buffer, from the oldest to
+    /// the newest one.
+    ///
+    /// Each element is only read from storage once the iterator advances to it.
+    ///
+    /// # Panics
+    ///
+    /// * If encoding an element exceeds the static buffer size.
+    pub fn iter(&self) -> impl Iterator<Item = V> + '_ {
+        let (head, len) = self.header();
+        (0..len).filter_map(move |index| self.elements.get(Self::slot(head, index)))
+    }
+}
+
+impl<V, const CAPACITY: u32, KeyType> ::core::fmt::Debug
+    for StorageRingBuffer<V, CAPACITY, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageRingBuffer")
+            .field("key", &KeyType::KEY)
+            .field("capacity", &CAPACITY)
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lazy::storage_rw,
+        traits::ManualKey,
+    };
+
+    #[test]
+    fn empty_ring_buffer_works_as_expected() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let buffer: StorageRingBuffer<u8, 3> = StorageRingBuffer::new();
+
+            assert_eq!(buffer.len(), 0);
+            assert_eq!(buffer.capacity(), 3);
+            assert!(buffer.is_empty());
+            assert_eq!(buffer.iter().next(), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn push_into_not_yet_full_buffer_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut buffer: StorageRingBuffer<u8, 3> = StorageRingBuffer::new();
+
+            buffer.push(&1);
+            buffer.push(&2);
+
+            assert_eq!(buffer.len(), 2);
+            assert_eq!(buffer.iter().collect::<Vec<_>>(), [1, 2]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn push_overwrites_oldest_elements() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut buffer: StorageRingBuffer<u8, 3> = StorageRingBuffer::new();
+
+            for value in 0..5 {
+                buffer.push(&value);
+            }
+
+            assert_eq!(buffer.len(), 3);
+            assert_eq!(buffer.iter().collect::<Vec<_>>(), [2, 3, 4]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn push_returns_evicted_element() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut buffer: StorageRingBuffer<u8, 3> = StorageRingBuffer::new();
+
+            assert_eq!(buffer.push(&1), None);
+            assert_eq!(buffer.push(&2), None);
+            assert_eq!(buffer.push(&3), None);
+            assert_eq!(buffer.push(&4), Some(1));
+
+            assert_eq!(buffer.len(), 3);
+            assert_eq!(buffer.get(0), Some(2));
+            assert_eq!(buffer.get(1), Some(3));
+            assert_eq!(buffer.get(2), Some(4));
+            assert_eq!(buffer.get(3), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn push_writes_one_slot_and_the_header() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut buffer: StorageRingBuffer<u8, 3> = StorageRingBuffer::new();
+
+            for value in 0..4 {
+                let (_, writes_before) = storage_rw();
+
+                buffer.push(&value);
+
+                let (_, writes_after) = storage_rw();
+                assert_eq!(writes_after - writes_before, 2);
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn push_works_for_two_buffers_with_same_manual_key() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut buffer: StorageRingBuffer<u8, 2, ManualKey<123>> =
+                StorageRingBuffer::new();
+            buffer.push(&1);
+
+            let buffer2: StorageRingBuffer<u8, 2, ManualKey<123>> =
+                StorageRingBuffer::new();
+            assert_eq!(buffer2.iter().collect::<Vec<_>>(), [1]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright (C) Use Ink (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simple storage set implementation built on top of [Mapping].
+//!
+//! # Note
+//!
+//! This set doesn't actually "own" any data.
+//! Instead it is just a simple wrapper around the contract storage facilities.
+
+use ink_primitives::Key;
+use ink_storage_traits::{
+    AutoKey,
+    Storable,
+    StorableHint,
+    StorageKey,
+};
+use scale::Encode;
+
+use crate::{
+    Lazy,
+    Mapping,
+};
+
+/// A set of values directly on contract storage.
+///
+/// Useful for membership tracking, such as allow lists or accounts which already
+/// voted.
+///
+/// # Important
+///
+/// [StorageSet] requires its own pre-defined storage key where to store values. By
+/// default, the is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey). Specifying the storage key can be helpful for
+/// upgradeable contracts or you want to be resistant to future changes of storage key
+/// calculation strategy.
+///
+/// # Storage Layout
+///
+/// At given [StorageKey] `K`, the number of elements in the [StorageSet] is hold.
+/// Each element `V` is marked as present by an empty storage cell under the key
+/// `E = scale::Encode((K, V))`.
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageSet<V, KeyType: StorageKey = AutoKey> {
+    /// The number of elements stored on-chain.
+    len: Lazy<u32, KeyType>,
+    /// We use a [Mapping] with empty values to mark the elements of the set.
+    elements: Mapping<V, (), KeyType>,
+}
+
+impl<V, KeyType> Default for StorageSet<V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> Storable for StorageSet<V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: scale::Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: scale::Input>(_input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Default::default())
+    }
+
+    #[inline]
+    fn encoded_size(&self) -> usize {
+        0
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageSet<V, InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageSet<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageSet<V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageSet<V, KeyType>
+    where
+        V: scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <() as StorageLayout>::layout(&KeyType::KEY),
+                scale_info::meta_type::<Self>(),
+            ))
+        }
+    }
+};
+
+impl<V, KeyType> StorageSet<V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageSet`.
+    pub const fn new() -> Self {
+        Self {
+            len: Lazy::new(),
+            elements: Mapping::new(),
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    pub fn len(&self) -> u32 {
+        self.len.get().unwrap_or(u32::MIN)
+    }
+
+    /// Returns `true` if the set contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<V, KeyType> StorageSet<V, KeyType>
+where
+    V: Encode,
+    KeyType: StorageKey,
+{
+    /// Adds `value` to the set.
+    ///
+    /// Returns `true` if the value was newly added. Adding a value which is already
+    /// part of the set doesn't write to the contract storage.
+    ///
+    /// # Panics
+    ///
+    /// * If the set is at capacity (max. of 2 ^ 32 elements).
+    /// * If the encoded `value` doesn't fit into the static buffer.
+    pub fn insert(&mut self, value: &V) -> bool {
+        if self.elements.contains(value) {
+            return false;
+        }
+
+        self.elements.insert(value, &());
+        self.len.set(&self.len().checked_add(1).unwrap());
+        true
+    }
+
+    /// Removes `value` from the set.
+    ///
+    /// Returns `true` if the value was part of the set.
+    ///
+    /// # Panics
+    ///
+    /// Traps if the encoded `value` doesn't fit into the static buffer.
+    pub fn remove(&mut self, value: &V) -> bool {
+        if !self.elements.contains(value) {
+            return false;
+        }
+
+        self.elements.remove(value);
+        self.len.set(&self.len().checked_sub(1).unwrap());
+        true
+    }
+
+    /// Returns `true` if `value` is part of the set.
+    ///
+    /// # Panics
+    ///
+    /// Traps if the encoded `value` doesn't fit into the static buffer.
+    pub fn contains(&self, value: &V) -> bool {
+        self.elements.contains(value)
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageSet<V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageSet")
+            .field("key", &KeyType::KEY)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lazy::storage_rw,
+        traits::ManualKey,
+    };
+
+    #[test]
+    fn empty_set_works_as_expected() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut set: StorageSet<u8> = StorageSet::new();
+
+            assert_eq!(set.len(), 0);
+            assert!(set.is_empty());
+            assert!(!set.contains(&1));
+            assert!(!set.remove(&1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn insert_contains_and_remove_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut set: StorageSet<u8> = StorageSet::new();
+
+            assert!(set.insert(&1));
+            assert!(set.contains(&1));
+            assert!(!set.contains(&2));
+            assert_eq!(set.len(), 1);
+
+            assert!(set.remove(&1));
+            assert!(!set.contains(&1));
+            assert_eq!(set.len(), 0);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn inserting_present_value_does_not_write() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut set: StorageSet<u8> = StorageSet::new();
+            assert!(set.insert(&1));
+            let (_, writes_before) = storage_rw();
+
+            assert!(!set.insert(&1));
+
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after, writes_before);
+            assert_eq!(set.len(), 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn insert_and_contains_work_for_two_sets_with_same_manual_key() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut set: StorageSet<u8, ManualKey<123>> = StorageSet::new();
+            set.insert(&1);
+
+            let set2: StorageSet<u8, ManualKey<123>> = StorageSet::new();
+            assert!(set2.contains(&1));
+            assert_eq!(set2.len(), 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
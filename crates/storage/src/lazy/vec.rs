@@ -19,7 +19,10 @@
 //! This vector doesn't actually "own" any data.
 //! Instead it is just a simple wrapper around the contract storage facilities.
 
-use core::cell::Cell;
+use core::{
+    borrow::Borrow,
+    cell::Cell,
+};
 use ink_primitives::Key;
 use ink_storage_traits::{
     AutoKey,
@@ -70,14 +73,17 @@ use crate::{
 ///
 /// # Caveats
 ///
-/// Iterators are not provided. [StorageVec] is expected to be used to
-/// store a lot elements, where iterating through the elements would be
-/// rather inefficient (naturally, it is still possible to manually
-/// iterate over the elements using a loop).
+/// [StorageVec] is expected to be used to store a lot elements, where
+/// iterating through the elements would be rather inefficient. The iterator
+/// returned by [`StorageVec::iter`] therefore loads each element individually
+/// from storage only once it is reached.
 ///
-/// For the same reason, operations which would require re-ordering
-/// stored elements are not supported. Examples include inserting and
-/// deleting elements at arbitrary positions or sorting elements.
+/// For the same reason, operations which re-order stored elements are
+/// expensive, as they need to load and store every element they move:
+/// [`StorageVec::insert`] and [`StorageVec::retain`] are O(n) in storage
+/// accesses. [`StorageVec::swap`] and [`StorageVec::swap_remove`] only touch
+/// two elements and [`StorageVec::binary_search`] loads O(log n) elements of a
+/// sorted vector. Sorting elements in place is not supported.
 ///
 /// The decision whether to use `Vec<T>` or [StorageVec] can be seen as an
 /// optimization problem with several factors:
@@ -279,6 +285,111 @@ where
         Ok(())
     }
 
+    /// Appends an element to the back of the vector, unless the vector already holds
+    /// `max_len` elements.
+    ///
+    /// Returns the index of the appended element, or `Err(StorageVecCapacityError)` if
+    /// the vector is full. In this case nothing is written.
+    ///
+    /// The bound is not stored with the vector. Contracts enforcing a bound should pass
+    /// the same constant on every call.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    /// * If there was already a value at the current index.
+    pub fn push_bounded<T>(
+        &mut self,
+        value: &T,
+        max_len: u32,
+    ) -> Result<u32, StorageVecCapacityError>
+    where
+        T: Storable + scale::EncodeLike<V>,
+    {
+        let slot = self.len();
+        if slot >= max_len {
+            return Err(StorageVecCapacityError);
+        }
+
+        self.push(value);
+        Ok(slot)
+    }
+
+    /// Initializes the empty vector with the given `items`.
+    ///
+    /// Unlike pushing the items one by one, the length is written only once, after
+    /// all elements were stored. Nothing is written if `items` is empty.
+    ///
+    /// # Panics
+    ///
+    /// * If the vector is not empty.
+    /// * If there are more than 2 ^ 32 items.
+    /// * If any value overgrows the static buffer size.
+    pub fn init_from_slice<T>(&mut self, items: &[T])
+    where
+        T: Storable + scale::EncodeLike<V>,
+    {
+        assert!(self.is_empty(), "StorageVec is not empty");
+
+        self.extend_from_slice(items);
+    }
+
+    /// Appends all `items` to the back of the vector.
+    ///
+    /// Unlike pushing the items one by one, the length is written only once, after
+    /// all elements were stored. Nothing is written if `items` is empty.
+    ///
+    /// # Panics
+    ///
+    /// * If the vector would exceed its capacity (max. of 2 ^ 32 elements).
+    /// * If any value overgrows the static buffer size.
+    /// * If there was already a value at any of the appended indices.
+    pub fn extend_from_slice<T>(&mut self, items: &[T])
+    where
+        T: Storable + scale::EncodeLike<V>,
+    {
+        self.append::<T, _>(items);
+    }
+
+    /// Appends all `items` of the iterator to the back of the vector.
+    ///
+    /// Unlike pushing the items one by one, the length is written only once, after
+    /// all elements were stored. Nothing is written if `items` is empty.
+    ///
+    /// # Panics
+    ///
+    /// * If the vector would exceed its capacity (max. of 2 ^ 32 elements).
+    /// * If any value overgrows the static buffer size.
+    /// * If there was already a value at any of the appended indices.
+    pub fn extend<T, I>(&mut self, items: I)
+    where
+        T: Storable + scale::EncodeLike<V>,
+        I: IntoIterator<Item = T>,
+    {
+        self.append::<T, _>(items);
+    }
+
+    /// Stores all `items` behind the last element, then writes the new length once.
+    fn append<T, I>(&mut self, items: I)
+    where
+        T: Storable + scale::EncodeLike<V>,
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let len = self.len();
+        let mut new_len = len;
+        for value in items {
+            let value: &T = value.borrow();
+            assert!(self.elements.insert(new_len, value).is_none());
+            new_len = new_len.checked_add(1).unwrap();
+        }
+
+        if new_len == len {
+            return;
+        }
+        self.set_len(new_len);
+    }
+
     /// Clears the last element from the storage and returns it.
     /// Shrinks the length of the vector by one.
     //
@@ -350,6 +461,34 @@ where
         self.elements.try_get(slot)
     }
 
+    /// Get a copy of the first element without removing it from storage.
+    ///
+    /// Only the first element is read from storage. Use [`StorageVec::last`] to get
+    /// the last element.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn first(&self) -> Option<V> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.elements.get(0)
+    }
+
+    /// Get a copy of the last element without removing it from storage.
+    ///
+    /// This is an alias of [`StorageVec::peek`]; only the last element is read from
+    /// storage.
+    ///
+    /// # Panics
+    ///
+    /// * If the value overgrows the static buffer size.
+    pub fn last(&self) -> Option<V> {
+        self.peek()
+    }
+
     /// Access an element at given `index`.
     ///
     /// Returns `None` if there was no value at the `index`.
@@ -373,6 +512,19 @@ where
         self.elements.try_get(index)
     }
 
+    /// Returns an iterator over the elements of the vector.
+    ///
+    /// Each element is only read from storage once the iterator advances to it,
+    /// so stopping early avoids loading the remaining elements. Elements that were
+    /// cleared from storage with [`StorageVec::clear_at`] are skipped.
+    ///
+    /// # Panics
+    ///
+    /// * If encoding an element exceeds the static buffer size.
+    pub fn iter(&self) -> impl Iterator<Item = V> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
     /// Set the `value` at given `index`.
     ///
     /// # Panics
@@ -425,10 +577,161 @@ where
     /// This iterates through all elements in the vector; complexity is O(n).
     /// It might not be possible to clear large vectors within a single block!
     pub fn clear(&mut self) {
-        for i in 0..self.len() {
+        self.truncate(0);
+    }
+
+    /// Shortens the vector to `len` elements, clearing the storage of all elements
+    /// past it.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    ///
+    /// # Warning
+    ///
+    /// This iterates through all removed elements; complexity is O(n).
+    /// It might not be possible to truncate large vectors within a single block!
+    pub fn truncate(&mut self, len: u32) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+
+        for i in len..old_len {
             self.elements.remove(i);
         }
-        self.set_len(0);
+        self.set_len(len);
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it up by one.
+    ///
+    /// Inserting at `index == len` is equivalent to [`StorageVec::push`].
+    ///
+    /// # Panics
+    ///
+    /// * If `index > len`.
+    /// * If the vector is at capacity (max. of 2 ^ 32 elements).
+    /// * If any value overgrows the static buffer size.
+    ///
+    /// # Caveats
+    ///
+    /// This moves all elements from `index` to the end; complexity is O(n).
+    /// It might not be possible to insert at the front of large vectors within a
+    /// single block!
+    pub fn insert<T>(&mut self, index: u32, value: &T)
+    where
+        T: Storable + EncodeLike<V>,
+    {
+        let len = self.len();
+        assert!(index <= len);
+
+        // Shift from the back, so that no element is overwritten before it was moved.
+        for i in (index..len).rev() {
+            match self.elements.get(i) {
+                Some(element) => {
+                    self.elements.insert(i + 1, &element);
+                }
+                None => self.elements.remove(i + 1),
+            }
+        }
+
+        self.elements.insert(index, value);
+        self.set_len(len.checked_add(1).unwrap());
+    }
+
+    /// Retains only the elements for which `f` returns `true`.
+    ///
+    /// The retained elements keep their order and are moved to the front of the
+    /// vector. Only slots whose element moved are rewritten, the vacated slots at the
+    /// end are cleared from storage. Elements that were already cleared from storage
+    /// are removed as well.
+    ///
+    /// # Panics
+    ///
+    /// * If encoding an element exceeds the static buffer size.
+    ///
+    /// # Caveats
+    ///
+    /// This loads every element; complexity is O(n).
+    /// It might not be possible to filter large vectors within a single block!
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&V) -> bool,
+    {
+        let mut retained = 0;
+        for index in 0..self.len() {
+            let element = match self.elements.get(index) {
+                Some(element) if f(&element) => element,
+                _ => continue,
+            };
+            if retained != index {
+                self.elements.insert(retained, &element);
+            }
+            retained += 1;
+        }
+
+        self.truncate(retained);
+    }
+
+    /// Removes the element at `index` and returns it, replacing it with the last
+    /// element of the vector.
+    ///
+    /// The storage slot of the last element is cleared and the length of the vector
+    /// shrinks by one. This does not preserve ordering, but is O(1).
+    ///
+    /// Returns `None` if the element at `index` was already cleared from storage.
+    ///
+    /// # Panics
+    ///
+    /// * If `index` exceeds the length of the vector.
+    /// * If the value overgrows the static buffer size.
+    pub fn swap_remove(&mut self, index: u32) -> Option<V> {
+        assert!(index < self.len());
+
+        let last = self.len().checked_sub(1).unwrap();
+        self.set_len(last);
+
+        if index == last {
+            return self.elements.take(last);
+        }
+
+        let removed = self.elements.get(index);
+        match self.elements.take(last) {
+            Some(last_value) => {
+                self.elements.insert(index, &last_value);
+            }
+            None => self.elements.remove(index),
+        }
+
+        removed
+    }
+
+    /// Swaps the elements at `a` and `b`.
+    ///
+    /// Both elements are loaded and written to the slot of the other one. Elements that
+    /// were cleared from storage stay cleared, so their slot is cleared at the other
+    /// index. Swapping an element with itself does not touch the storage.
+    ///
+    /// # Panics
+    ///
+    /// * If `a` or `b` exceed the length of the vector.
+    /// * If the value overgrows the static buffer size.
+    pub fn swap(&mut self, a: u32, b: u32) {
+        let len = self.len();
+        assert!(a < len && b < len);
+
+        if a == b {
+            return;
+        }
+
+        let value_a = self.elements.get(a);
+        let value_b = self.elements.get(b);
+        for (index, value) in [(a, value_b), (b, value_a)] {
+            match value {
+                Some(value) => {
+                    self.elements.insert(index, &value);
+                }
+                None => self.elements.remove(index),
+            }
+        }
     }
 
     /// Clears the value of the element at `index`. It doesn't change the length of the
@@ -444,6 +747,53 @@ where
     }
 }
 
+impl<V, KeyType> StorageVec<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    /// Binary searches this sorted vector for the given `value`.
+    ///
+    /// Returns `Ok` with the index of a matching element, or `Err` with the index
+    /// where `value` could be inserted while maintaining the sort order. If there are
+    /// multiple matches, any one of them may be returned.
+    ///
+    /// Only the probed elements are read from storage, which are at most
+    /// `log2(len) + 1` elements.
+    ///
+    /// # Panics
+    ///
+    /// * If an element within bounds was cleared from storage.
+    /// * If encoding an element exceeds the static buffer size.
+    pub fn binary_search(&self, value: &V) -> Result<u32, u32> {
+        let mut left = 0;
+        let mut right = self.len();
+        while left < right {
+            let mid = left + (right - left) / 2;
+            let element = self
+                .get(mid)
+                .expect("StorageVec::binary_search: missing element within bounds");
+            match element.cmp(value) {
+                core::cmp::Ordering::Less => left = mid + 1,
+                core::cmp::Ordering::Greater => right = mid,
+                core::cmp::Ordering::Equal => return Ok(mid),
+            }
+        }
+
+        Err(left)
+    }
+}
+
+/// The error returned by [`StorageVec::push_bounded`] if the vector is full.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StorageVecCapacityError;
+
+impl core::fmt::Display for StorageVecCapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("the StorageVec is full")
+    }
+}
+
 impl<V, KeyType> FromIterator<V> for StorageVec<V, KeyType>
 where
     V: Packed + EncodeLike<V>,
@@ -477,7 +827,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::traits::ManualKey;
+    use crate::{
+        lazy::storage_rw,
+        traits::ManualKey,
+    };
 
     #[test]
     fn empty_vec_works_as_expected() {
@@ -509,6 +862,27 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn push_bounded_rejects_when_full() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = StorageVec::new();
+
+            assert_eq!(array.push_bounded(&1, 2), Ok(0));
+            assert_eq!(array.push_bounded(&2, 2), Ok(1));
+
+            let (_, writes_before) = storage_rw();
+            assert_eq!(array.push_bounded(&3, 2), Err(StorageVecCapacityError));
+            let (_, writes_after) = storage_rw();
+
+            assert_eq!(writes_after, writes_before);
+            assert_eq!(array.len(), 2);
+            assert_eq!(array.peek(), Some(2));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn storage_keys_are_correct() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -605,6 +979,373 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn truncate_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+            let (_, writes_before) = storage_rw();
+
+            array.truncate(2);
+
+            // Three element slots are cleared and the length is written once.
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after - writes_before, 4);
+            assert_eq!(array.len(), 2);
+            for i in 0..2 {
+                assert_eq!(array.get(i), Some(i as u8));
+            }
+            for i in 2..5 {
+                assert_eq!(array.get(i), None);
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn truncate_to_larger_len_does_nothing() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+            let (_, writes_before) = storage_rw();
+
+            array.truncate(5);
+            array.truncate(10);
+
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after, writes_before);
+            assert_eq!(array.len(), 5);
+            assert_eq!(array.get(4), Some(4));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+            array.clear_at(3);
+
+            let elements: Vec<_> = array.iter().collect();
+
+            assert_eq!(elements, [0, 1, 2, 4]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_loads_only_consumed_elements() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let array: StorageVec<u8> = (0..5).collect();
+
+            let iter = array.iter();
+            let (reads_before, _) = storage_rw();
+            let elements: Vec<_> = iter.take(2).collect();
+            let (reads_after, _) = storage_rw();
+
+            assert_eq!(elements, [0, 1]);
+            assert_eq!(reads_after - reads_before, 2);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn init_from_slice_writes_length_once() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = StorageVec::new();
+            let (_, writes_before) = storage_rw();
+
+            array.init_from_slice(&[1u8, 2, 3, 4, 5]);
+
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after - writes_before, 6);
+            assert_eq!(array.len(), 5);
+            assert_eq!(
+                array.iter().collect::<Vec<_>>(),
+                (1..=5).collect::<Vec<_>>()
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "StorageVec is not empty")]
+    fn init_from_slice_panics_on_non_empty_vec() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..1).collect();
+
+            array.init_from_slice(&[1u8, 2]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn extend_writes_length_once() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (1..=2).collect();
+            let (_, writes_before) = storage_rw();
+
+            array.extend_from_slice(&[3u8, 4, 5]);
+
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after - writes_before, 4);
+
+            array.extend(6u8..=7);
+
+            let (_, writes_final) = storage_rw();
+            assert_eq!(writes_final - writes_after, 3);
+            assert_eq!(array.len(), 7);
+            assert_eq!(
+                array.iter().collect::<Vec<_>>(),
+                (1..=7).collect::<Vec<_>>()
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn extend_with_nothing_does_not_write() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = StorageVec::new();
+            let (_, writes_before) = storage_rw();
+
+            array.init_from_slice::<u8>(&[]);
+            array.extend_from_slice::<u8>(&[]);
+            array.extend(core::iter::empty::<u8>());
+
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after, writes_before);
+            assert!(array.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn binary_search_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let array: StorageVec<u8> = (0..16).map(|i| i * 2).collect();
+
+            for index in 0..16u32 {
+                assert_eq!(array.binary_search(&(index as u8 * 2)), Ok(index));
+            }
+            assert_eq!(array.binary_search(&7), Err(4));
+            assert_eq!(array.binary_search(&255), Err(16));
+
+            let empty: StorageVec<u8, ManualKey<123>> = StorageVec::new();
+            assert_eq!(empty.binary_search(&1), Err(0));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn binary_search_loads_logarithmic_elements() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let array: StorageVec<u8> = (0..16).map(|i| i * 2).collect();
+
+            for value in [0, 13, 30, 31] {
+                let (reads_before, _) = storage_rw();
+                let _ = array.binary_search(&value);
+                let (reads_after, _) = storage_rw();
+
+                // The cached length is checked against storage in debug builds.
+                let len_reads = cfg!(debug_assertions) as usize;
+                assert!(
+                    reads_after - reads_before <= 16u32.ilog2() as usize + 1 + len_reads
+                );
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn insert_shifts_subsequent_elements() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..4).collect();
+
+            array.insert(1, &9);
+
+            assert_eq!(array.len(), 5);
+            assert_eq!(array.iter().collect::<Vec<_>>(), [0, 9, 1, 2, 3]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn insert_at_len_pushes() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..2).collect();
+
+            array.insert(2, &9);
+
+            assert_eq!(array.len(), 3);
+            assert_eq!(array.peek(), Some(9));
+            assert_eq!(array.iter().collect::<Vec<_>>(), [0, 1, 9]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_beyond_len_panics() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            StorageVec::<u8>::new().insert(1, &9);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn retain_compacts_and_clears_tail() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            const BASE: u32 = 123;
+            let mut array: StorageVec<u8, ManualKey<BASE>> = StorageVec::new();
+            array.extend_from_slice(&[1u8, 2, 3, 4]);
+            let (_, writes_before) = storage_rw();
+
+            array.retain(|value| value % 2 == 0);
+
+            let (_, writes_after) = storage_rw();
+            // Two moved elements, two cleared slots and the length.
+            assert_eq!(writes_after - writes_before, 5);
+            assert_eq!(array.len(), 2);
+            assert_eq!(array.iter().collect::<Vec<_>>(), [2, 4]);
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, 2u32)), None);
+            assert_eq!(ink_env::contains_contract_storage(&(BASE, 3u32)), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn retain_without_removals_does_not_write() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..4).collect();
+            let (_, writes_before) = storage_rw();
+
+            array.retain(|_| true);
+
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after, writes_before);
+            assert_eq!(array.len(), 4);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn swap_remove_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+
+            assert_eq!(array.swap_remove(1), Some(1));
+            assert_eq!(array.len(), 4);
+            assert_eq!(array.get(1), Some(4));
+            assert_eq!(array.get(4), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn swap_remove_last_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+
+            assert_eq!(array.swap_remove(4), Some(4));
+            assert_eq!(array.len(), 4);
+            assert_eq!(array.get(3), Some(3));
+            assert_eq!(array.get(4), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_remove_invalid_index_panics() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            StorageVec::<u8>::new().swap_remove(0);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn swap_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+
+            array.swap(1, 3);
+            assert_eq!(array.iter().collect::<Vec<_>>(), [0, 3, 2, 1, 4]);
+
+            array.clear_at(0);
+            array.swap(0, 4);
+            assert_eq!(array.get(0), Some(4));
+            assert_eq!(array.get(4), None);
+            assert_eq!(array.len(), 5);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn swap_with_itself_does_not_write() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+            let (_, writes_before) = storage_rw();
+
+            array.swap(2, 2);
+
+            let (_, writes_after) = storage_rw();
+            assert_eq!(writes_after, writes_before);
+            assert_eq!(array.get(2), Some(2));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_invalid_index_panics() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = (0..5).collect();
+            array.swap(0, 5);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn clear_at_works() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -687,6 +1428,85 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn first_loads_only_the_first_element() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            const BASE: u32 = 123;
+            let mut array: StorageVec<u8, ManualKey<BASE>> = StorageVec::new();
+            assert_eq!(array.first(), None);
+            array.extend_from_slice(&[1u8, 2, 3]);
+
+            // A fresh handle has to load the length and the first element.
+            let array: StorageVec<u8, ManualKey<BASE>> = StorageVec::new();
+            let (reads_before, _) = storage_rw();
+            assert_eq!(array.first(), Some(1));
+            let (reads_after, _) = storage_rw();
+            assert_eq!(reads_after - reads_before, 2);
+
+            // With the length cached, only the first element is loaded. In debug
+            // builds the cached length is checked against storage.
+            let (reads_before, _) = storage_rw();
+            assert_eq!(array.first(), Some(1));
+            let (reads_after, _) = storage_rw();
+            assert_eq!(
+                reads_after - reads_before,
+                1 + cfg!(debug_assertions) as usize
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn last_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut array: StorageVec<u8> = StorageVec::new();
+            assert_eq!(array.last(), None);
+
+            array.push(&1);
+            assert_eq!(array.last(), Some(1));
+            array.push(&2);
+            assert_eq!(array.last(), Some(2));
+            assert_eq!(array.last(), array.peek());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn last_loads_only_the_last_element() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            const BASE: u32 = 123;
+            let mut array: StorageVec<u8, ManualKey<BASE>> = StorageVec::new();
+            array.extend_from_slice(&[1u8, 2, 3]);
+
+            // A fresh handle has to load the length and the last element. In debug
+            // builds the cached length is checked against storage.
+            let array: StorageVec<u8, ManualKey<BASE>> = StorageVec::new();
+            let (reads_before, _) = storage_rw();
+            assert_eq!(array.last(), Some(3));
+            let (reads_after, _) = storage_rw();
+            assert_eq!(
+                reads_after - reads_before,
+                2 + cfg!(debug_assertions) as usize
+            );
+
+            // With the length cached, only the last element is loaded.
+            let (reads_before, _) = storage_rw();
+            assert_eq!(array.last(), Some(3));
+            let (reads_after, _) = storage_rw();
+            assert_eq!(
+                reads_after - reads_before,
+                1 + 2 * cfg!(debug_assertions) as usize
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn peek_works() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
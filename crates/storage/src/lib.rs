@@ -54,5 +54,12 @@ pub(crate) mod lazy;
 pub use self::lazy::{
     Lazy,
     Mapping,
+    StorageArray,
+    StorageDeque,
+    StorageHeap,
+    StorageLinkedList,
+    StorageRingBuffer,
+    StorageSet,
     StorageVec,
+    StorageVecCapacityError,
 };